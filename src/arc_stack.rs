@@ -0,0 +1,166 @@
+use alloc::sync::Arc;
+
+// PersistentStack の Rc を Arc に置き換えたもの
+// T: Send + Sync ならスレッドをまたいで共有できる
+pub struct ArcStack<T> {
+    head: Link<T>,
+}
+
+type Link<T> = Option<Arc<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+impl<T> ArcStack<T> {
+    pub fn new() -> Self {
+        ArcStack { head: None }
+    }
+
+    pub fn prepend(&self, elem: T) -> Self {
+        ArcStack {
+            head: Some(Arc::new(Node {
+                elem,
+                next: self.head.as_ref().map(Arc::clone),
+            })),
+        }
+    }
+
+    pub fn tail(&self) -> Self {
+        ArcStack {
+            head: self
+                .head
+                .as_ref()
+                .and_then(|node| node.next.as_ref().map(Arc::clone)),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<T> ArcStack<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<T> Clone for ArcStack<T> {
+    fn clone(&self) -> Self {
+        ArcStack {
+            head: self.head.as_ref().map(Arc::clone),
+        }
+    }
+}
+
+impl<T> Default for ArcStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ArcStack<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        // try_unwrap だと、最後の 2 つのハンドルを別スレッドが同時に落としたときに
+        // 両方が失敗して break し、残りが再帰的に解放されてしまう
+        // into_inner ならどちらか一方が必ずノードを受け取れる
+        while let Some(node) = head {
+            match Arc::into_inner(node) {
+                Some(mut node) => head = node.next.take(),
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_arc_stack {
+    use super::ArcStack;
+    use alloc::{vec, vec::Vec};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[test]
+    fn basics() {
+        let stack = ArcStack::new();
+        assert_eq!(stack.head(), None);
+
+        let stack = stack.prepend(1).prepend(2).prepend(3);
+        assert_eq!(stack.head(), Some(&3));
+
+        let stack = stack.tail();
+        assert_eq!(stack.head(), Some(&2));
+
+        let stack = stack.tail();
+        assert_eq!(stack.head(), Some(&1));
+
+        let stack = stack.tail();
+        assert_eq!(stack.head(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let stack = ArcStack::new().prepend(1).prepend(2).prepend(3);
+
+        let mut iter = stack.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+    }
+
+    #[test]
+    fn send_across_threads() {
+        let stack = ArcStack::new().prepend(1).prepend(2).prepend(3);
+
+        let shared = stack.clone();
+        let handle = thread::spawn(move || {
+            let shared = shared.prepend(4);
+            shared.iter().copied().collect::<Vec<_>>()
+        });
+        assert_eq!(handle.join().unwrap(), vec![4, 3, 2, 1]);
+
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn concurrent_drop_long_chain() {
+        const THREADS: usize = 8;
+        let stack = (0..100_000).fold(ArcStack::new(), |stack, i| stack.prepend(i));
+        let barrier = Arc::new(Barrier::new(THREADS));
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let shared = stack.clone();
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    drop(shared);
+                })
+            })
+            .collect();
+        drop(stack);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -1,19 +1,27 @@
-use std::{
-    cell::{Ref, RefCell, RefMut},
-    rc::Rc,
+use alloc::{
+    rc::{Rc, Weak},
+    vec::Vec,
 };
+use core::cell::{Ref, RefCell, RefMut};
+#[cfg(feature = "std")]
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
 
 pub struct BadSafeDeque<T> {
     head: Link<T>,
     tail: Link<T>,
+    len: usize,
 }
 
 type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+// prev は弱参照にして、next との間で強参照の循環ができないようにする
+type WeakLink<T> = Option<Weak<RefCell<Node<T>>>>;
 
 struct Node<T> {
     elem: T,
     next: Link<T>,
-    prev: Link<T>,
+    prev: WeakLink<T>,
 }
 
 impl<T> Node<T> {
@@ -31,6 +39,7 @@ impl<T> BadSafeDeque<T> {
         BadSafeDeque {
             head: None,
             tail: None,
+            len: 0,
         }
     }
 
@@ -41,7 +50,7 @@ impl<T> BadSafeDeque<T> {
             // 先頭の要素がある場合は、その要素の prev に追加する要素をセットし、
             // 追加する要素の next に先頭の要素をセットする
             Some(old_head) => {
-                old_head.borrow_mut().prev = Some(Rc::clone(&new_head));
+                old_head.borrow_mut().prev = Some(Rc::downgrade(&new_head));
                 new_head.borrow_mut().next = Some(old_head);
                 self.head = Some(new_head);
             }
@@ -51,6 +60,7 @@ impl<T> BadSafeDeque<T> {
                 self.head = Some(new_head);
             }
         }
+        self.len += 1;
     }
 
     pub fn push_back(&mut self, elem: T) {
@@ -58,7 +68,7 @@ impl<T> BadSafeDeque<T> {
         match self.tail.take() {
             Some(old_tail) => {
                 old_tail.borrow_mut().next = Some(Rc::clone(&new_tail));
-                new_tail.borrow_mut().prev = Some(old_tail);
+                new_tail.borrow_mut().prev = Some(Rc::downgrade(&old_tail));
                 self.tail = Some(new_tail);
             }
             None => {
@@ -66,6 +76,7 @@ impl<T> BadSafeDeque<T> {
                 self.head = Some(new_tail);
             }
         }
+        self.len += 1;
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
@@ -83,13 +94,15 @@ impl<T> BadSafeDeque<T> {
                     self.tail.take();
                 }
             }
+            self.len -= 1;
             Rc::try_unwrap(old_head).ok().unwrap().into_inner().elem
         })
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
         self.tail.take().map(|old_tail| {
-            match old_tail.borrow_mut().prev.take() {
+            let prev = old_tail.borrow_mut().prev.take();
+            match prev.as_ref().and_then(Weak::upgrade) {
                 Some(new_tail) => {
                     new_tail.borrow_mut().next.take();
                     self.tail = Some(new_tail);
@@ -98,45 +111,491 @@ impl<T> BadSafeDeque<T> {
                     self.head.take();
                 }
             }
+            self.len -= 1;
             Rc::try_unwrap(old_tail).ok().unwrap().into_inner().elem
         })
     }
 
-    pub fn peek_front(&self) -> Option<Ref<T>> {
+    // 判定用の Ref は pop する前に手放す（借りたままだと pop_* の borrow_mut が失敗する）
+    pub fn pop_front_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        let matched = self.peek_front().is_some_and(|elem| pred(&elem));
+        if matched {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    pub fn pop_back_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        let matched = self.peek_back().is_some_and(|elem| pred(&elem));
+        if matched {
+            self.pop_back()
+        } else {
+            None
+        }
+    }
+
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
         self.head
             .as_ref()
             .map(|node| Ref::map(node.borrow(), |node| &node.elem))
     }
 
-    pub fn peek_back(&self) -> Option<Ref<T>> {
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
         self.tail
             .as_ref()
             .map(|node| Ref::map(node.borrow(), |node| &node.elem))
     }
 
-    pub fn peek_front_mut(&self) -> Option<RefMut<T>> {
+    pub fn peek_front_mut(&self) -> Option<RefMut<'_, T>> {
         self.head
             .as_ref()
             .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
     }
 
-    pub fn peek_back_mut(&self) -> Option<RefMut<T>> {
+    pub fn peek_back_mut(&self) -> Option<RefMut<'_, T>> {
         self.tail
             .as_ref()
             .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
     }
+
+    // VecDeque と同じ名前で使えるように peek_* へ委譲する
+    pub fn front(&self) -> Option<Ref<'_, T>> {
+        self.peek_front()
+    }
+
+    pub fn back(&self) -> Option<Ref<'_, T>> {
+        self.peek_back()
+    }
+
+    pub fn front_mut(&self) -> Option<RefMut<'_, T>> {
+        self.peek_front_mut()
+    }
+
+    pub fn back_mut(&self) -> Option<RefMut<'_, T>> {
+        self.peek_back_mut()
+    }
+
+    // head と tail のうち近い方から辿って index 番目の要素を借りる
+    pub fn get(&self, index: usize) -> Option<Ref<'_, T>> {
+        if index >= self.len {
+            return None;
+        }
+        // 途中のノードは Ref を返せないので、借用ガードを作らずに辿る
+        // SAFETY: リンクを書き換えるメソッドはすべて &mut self を取るので、
+        // &self を借りている間は途中のノードも目的のノードも解放されない
+        let node = if index < self.len - index {
+            let mut node = &**self.head.as_ref()?;
+            for _ in 0..index {
+                let cur = unsafe { node.try_borrow_unguarded() }.expect("already mutably borrowed");
+                node = cur.next.as_deref()?;
+            }
+            node
+        } else {
+            let mut node = &**self.tail.as_ref()?;
+            for _ in index + 1..self.len {
+                let cur = unsafe { node.try_borrow_unguarded() }.expect("already mutably borrowed");
+                node = unsafe { &*cur.prev.as_ref()?.as_ptr() };
+            }
+            node
+        };
+        Some(Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    // 先頭と末尾の要素の値を入れ替える（リンクは触らない）
+    pub fn swap_ends(&mut self) {
+        if let (Some(head), Some(tail)) = (&self.head, &self.tail) {
+            if !Rc::ptr_eq(head, tail) {
+                core::mem::swap(&mut head.borrow_mut().elem, &mut tail.borrow_mut().elem);
+            }
+        }
+    }
+
+    // 先頭から n % len 個を取り出して末尾に積み直す
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        for _ in 0..n % len {
+            let elem = self.pop_front().unwrap();
+            self.push_back(elem);
+        }
+    }
+
+    // 末尾から n % len 個を取り出して先頭に積み直す
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        for _ in 0..n % len {
+            let elem = self.pop_back().unwrap();
+            self.push_front(elem);
+        }
+    }
+
+    // self の先頭と other の末尾から交互に取り出して self に並べ直す
+    pub fn weave(&mut self, other: &mut BadSafeDeque<T>) {
+        let mut front = core::mem::take(self);
+        loop {
+            let a = front.pop_front();
+            let b = other.pop_back();
+            if a.is_none() && b.is_none() {
+                break;
+            }
+            for elem in a.into_iter().chain(b) {
+                self.push_back(elem);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 先頭から順に要素を借りて f に渡す
+    fn for_each_elem<F: FnMut(&T)>(&self, mut f: F) {
+        let mut cur = self.head.as_ref().map(Rc::clone);
+        while let Some(node) = cur {
+            let node = node.borrow();
+            f(&node.elem);
+            cur = node.next.as_ref().map(Rc::clone);
+        }
+    }
+
+    // 先頭から読んだときに回文になっている連続区間のうち、最も長いものの長さ
+    pub fn longest_palindromic_run(&self) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let mut elems = Vec::with_capacity(self.len);
+        self.for_each_elem(|elem| elems.push(elem.clone()));
+
+        // 各要素（奇数長）と各要素の間（偶数長）を中心に左右へ広げる
+        let mut longest = 0;
+        for center in 0..2 * elems.len() {
+            let (mut left, mut right) = (center / 2, center / 2 + center % 2);
+            while right < elems.len() && elems[left] == elems[right] {
+                longest = longest.max(right - left + 1);
+                if left == 0 {
+                    break;
+                }
+                left -= 1;
+                right += 1;
+            }
+        }
+        longest
+    }
+
+    // 先頭から長さ k の窓をずらしながら、各窓のハッシュを求める
+    // 要素ごとのハッシュ h を基数 BASE の多項式として扱い、窓をずらすときは
+    // 出ていく要素の項を引いて BASE 倍し、入ってくる要素を足す（mod 2^64）
+    #[cfg(feature = "std")]
+    pub fn rolling_hashes(&self, k: usize) -> Vec<u64>
+    where
+        T: Hash,
+    {
+        const BASE: u64 = 1_000_003;
+        if k == 0 || k > self.len {
+            return Vec::new();
+        }
+
+        let mut elem_hashes = Vec::with_capacity(self.len);
+        self.for_each_elem(|elem| {
+            let mut hasher = DefaultHasher::new();
+            elem.hash(&mut hasher);
+            elem_hashes.push(hasher.finish());
+        });
+
+        // 窓から出ていく要素の重み BASE^(k-1)
+        let top = (1..k).fold(1u64, |acc, _| acc.wrapping_mul(BASE));
+        let mut hash = elem_hashes[..k]
+            .iter()
+            .fold(0u64, |acc, &h| acc.wrapping_mul(BASE).wrapping_add(h));
+        let mut hashes = alloc::vec![hash];
+        for i in k..elem_hashes.len() {
+            hash = hash
+                .wrapping_sub(elem_hashes[i - k].wrapping_mul(top))
+                .wrapping_mul(BASE)
+                .wrapping_add(elem_hashes[i]);
+            hashes.push(hash);
+        }
+        hashes
+    }
+
+    // 先頭から一つずつ外していくので、長いリストでも再帰的な drop にならない
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    // 1 つずつ push_front するので、iter の最後の要素が先頭になる（入力とは逆順に並ぶ）
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_front(elem);
+        }
+    }
+
+    // iter の順序のまま末尾に並ぶ
+    pub fn extend_back<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+
+    // 中身をすべて捨て、iter の要素を先頭から順に並べ直す
+    pub fn replace_all<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.clear();
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+
+    // self の末尾に other のノード列をそのまま繋ぎ、other を空にする
+    pub fn append(&mut self, other: &mut BadSafeDeque<T>) {
+        match (self.tail.take(), other.head.take()) {
+            (Some(tail), Some(head)) => {
+                head.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(head);
+                self.tail = other.tail.take();
+            }
+            (Some(tail), None) => self.tail = Some(tail),
+            (None, head) => {
+                self.head = head;
+                self.tail = other.tail.take();
+            }
+        }
+        self.len += core::mem::take(&mut other.len);
+    }
+
+    // self の先頭に other のノード列をそのまま繋ぎ、other を空にする
+    pub fn prepend_all(&mut self, other: &mut BadSafeDeque<T>) {
+        other.append(self);
+        core::mem::swap(self, other);
+    }
+
+    // 先頭から数えて len / 2 番目以降の後半だけを、リンクの付け替えで反転する
+    pub fn reverse_back_half(&mut self) {
+        let len = self.len();
+        let back_len = len - len / 2;
+        if back_len < 2 {
+            return;
+        }
+
+        // 末尾から prev を辿って後半のノードを集める（集めた順が反転後の並びになる）
+        let mut back = Vec::with_capacity(back_len);
+        let mut cur = self.tail.as_ref().map(Rc::clone);
+        for _ in 0..back_len {
+            let node = cur.unwrap();
+            cur = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+            back.push(node);
+        }
+        // cur は前半の最後のノード（前半が空なら None）
+        for (i, node) in back.iter().enumerate() {
+            let mut node = node.borrow_mut();
+            node.next = back.get(i + 1).map(Rc::clone);
+            node.prev = match i {
+                0 => cur.as_ref().map(Rc::downgrade),
+                _ => Some(Rc::downgrade(&back[i - 1])),
+            };
+        }
+        match cur {
+            Some(node) => node.borrow_mut().next = Some(Rc::clone(&back[0])),
+            None => self.head = Some(Rc::clone(&back[0])),
+        }
+        self.tail = back.pop();
+    }
+
+    // next を辿ったときに循環していないかをフロイドの循環検出法で確認する
+    // （リンク操作のバグで壊れていないかを調べるためのデバッグ用）
+    pub fn is_acyclic(&self) -> bool {
+        let next = |node: &Rc<RefCell<Node<T>>>| node.borrow().next.as_ref().map(Rc::clone);
+        let mut slow = self.head.as_ref().map(Rc::clone);
+        let mut fast = self.head.as_ref().map(Rc::clone);
+        loop {
+            // fast は 2 つずつ、slow は 1 つずつ進め、末尾に届けば循環はない
+            fast = match fast.as_ref().and_then(next).as_ref().and_then(next) {
+                Some(node) => Some(node),
+                None => return true,
+            };
+            slow = slow.as_ref().and_then(next);
+            if let (Some(slow), Some(fast)) = (&slow, &fast) {
+                if Rc::ptr_eq(slow, fast) {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+// 先頭から順にノードを一時的に borrow して比べる
+// どちらかの要素が RefMut で借りられたままだと borrow が panic する
+impl<T: PartialEq> PartialEq for BadSafeDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+        let mut a_cur = self.head.as_ref().map(Rc::clone);
+        let mut b_cur = other.head.as_ref().map(Rc::clone);
+        while let (Some(a_node), Some(b_node)) = (a_cur, b_cur) {
+            let (a_node, b_node) = (a_node.borrow(), b_node.borrow());
+            if a_node.elem != b_node.elem {
+                return false;
+            }
+            a_cur = a_node.next.as_ref().map(Rc::clone);
+            b_cur = b_node.next.as_ref().map(Rc::clone);
+        }
+        true
+    }
+}
+
+impl<T> Default for BadSafeDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> Drop for BadSafeDeque<T> {
     fn drop(&mut self) {
-        while self.pop_front().is_some() {}
+        self.clear();
     }
 }
 
-pub struct IntoIter<T>(BadSafeDeque<T>);
+// 任意の位置を指して挿入・削除するためのカーソル
+// 末尾の次（先頭の前）は要素を指さない位置（None）として扱う
+pub struct Cursor<'a, T> {
+    deque: &'a mut BadSafeDeque<T>,
+    cur: Link<T>,
+}
+
+impl<T> BadSafeDeque<T> {
+    pub fn cursor_front_mut(&mut self) -> Cursor<'_, T> {
+        let cur = self.head.as_ref().map(Rc::clone);
+        Cursor { deque: self, cur }
+    }
+}
+
+impl<T> Cursor<'_, T> {
+    pub fn move_next(&mut self) {
+        self.cur = match self.cur.take() {
+            Some(node) => node.borrow().next.as_ref().map(Rc::clone),
+            None => self.deque.head.as_ref().map(Rc::clone),
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.cur = match self.cur.take() {
+            Some(node) => node.borrow().prev.as_ref().and_then(Weak::upgrade),
+            None => self.deque.tail.as_ref().map(Rc::clone),
+        };
+    }
+
+    pub fn current(&self) -> Option<Ref<'_, T>> {
+        self.cur
+            .as_ref()
+            .map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    // 現在の要素の直後に挿入する（要素を指していないときは先頭に挿入する）
+    pub fn insert_after(&mut self, elem: T) {
+        let node = match &self.cur {
+            Some(node) => node,
+            None => return self.deque.push_front(elem),
+        };
+        let next = node.borrow_mut().next.take();
+        match next {
+            Some(next) => {
+                let new_node = Node::new(elem);
+                next.borrow_mut().prev = Some(Rc::downgrade(&new_node));
+                new_node.borrow_mut().next = Some(next);
+                new_node.borrow_mut().prev = Some(Rc::downgrade(node));
+                node.borrow_mut().next = Some(new_node);
+                self.deque.len += 1;
+            }
+            None => self.deque.push_back(elem),
+        }
+    }
+
+    // 現在の要素を取り除いて返し、カーソルは次の要素に進める
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.cur.take()?;
+        let prev = node
+            .borrow_mut()
+            .prev
+            .take()
+            .and_then(|prev| prev.upgrade());
+        let next = node.borrow_mut().next.take();
+
+        // 前後のノード（なければ head / tail）を繋ぎ直して、node への参照を外す
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.as_ref().map(Rc::clone),
+            None => self.deque.head = next.as_ref().map(Rc::clone),
+        }
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.deque.tail = prev,
+        }
+        self.deque.len -= 1;
+        self.cur = next;
+
+        Some(Rc::try_unwrap(node).ok().unwrap().into_inner().elem)
+    }
+}
+
+// カーソルは現在のノードの Rc を握っているので、Drop を実装してカーソルが生きている間は
+// deque の借用も続くようにする（古いカーソルが残ったままだと remove_current の try_unwrap が失敗する）
+impl<T> Drop for Cursor<'_, T> {
+    fn drop(&mut self) {
+        self.cur.take();
+    }
+}
+
+// tail から prev を辿って要素を借りていく
+pub struct IterBack<'a, T> {
+    next: Option<&'a RefCell<Node<T>>>,
+    len: usize,
+}
 
 impl<T> BadSafeDeque<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
+    pub fn iter_back(&self) -> IterBack<'_, T> {
+        IterBack {
+            next: self.tail.as_deref(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterBack<'a, T> {
+    type Item = Ref<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        // get と同じく、次のノードへは借用ガードを作らずに辿る
+        // SAFETY: &'a BadSafeDeque を借りている間はリンクが書き換えられず、ノードも解放されない
+        let cur = unsafe { node.try_borrow_unguarded() }.expect("already mutably borrowed");
+        self.next = cur.prev.as_ref().map(|prev| unsafe { &*prev.as_ptr() });
+        self.len -= 1;
+        Some(Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterBack<'_, T> {}
+
+pub struct IntoIter<T>(BadSafeDeque<T>);
+
+impl<T> IntoIterator for BadSafeDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
         IntoIter(self)
     }
 }
@@ -147,8 +606,15 @@ impl<T> Iterator for IntoIter<T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop_front()
     }
+
+    // 残りの要素数はデックの len そのもの
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
 impl<T> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.0.pop_back()
@@ -158,6 +624,26 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
 #[cfg(test)]
 mod test_bad_safe_deque {
     use super::BadSafeDeque;
+    use alloc::{vec, vec::Vec};
+    use std::rc::Rc;
+
+    // elems を先頭から順に並べたデックを作る
+    fn deque_of<I: IntoIterator<Item = i32>>(elems: I) -> BadSafeDeque<i32> {
+        let mut deque = BadSafeDeque::new();
+        deque.extend_back(elems);
+        deque
+    }
+
+    // 末尾の next を先頭に繋いで循環を作る
+    fn force_cycle<T>(deque: &mut BadSafeDeque<T>) {
+        let head = Rc::clone(deque.head.as_ref().unwrap());
+        deque.tail.as_ref().unwrap().borrow_mut().next = Some(head);
+    }
+
+    // force_cycle で作った循環を切る
+    fn break_cycle<T>(deque: &mut BadSafeDeque<T>) {
+        deque.tail.as_ref().unwrap().borrow_mut().next.take();
+    }
 
     #[test]
     fn basics() {
@@ -224,4 +710,469 @@ mod test_bad_safe_deque {
         assert_eq!(iter.next(), None);
         assert_eq!(iter.next_back(), None);
     }
+
+    #[test]
+    fn is_acyclic() {
+        let mut deque = BadSafeDeque::new();
+        assert!(deque.is_acyclic());
+
+        deque.push_back(1);
+        assert!(deque.is_acyclic());
+
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_back(4);
+        assert!(deque.is_acyclic());
+
+        force_cycle(&mut deque);
+        assert!(!deque.is_acyclic());
+
+        break_cycle(&mut deque);
+        assert!(deque.is_acyclic());
+    }
+
+    #[test]
+    fn reverse_back_half() {
+        let mut deque = deque_of(1..=6);
+        deque.reverse_back_half();
+        assert!(deque.is_acyclic());
+        assert_eq!(*deque.peek_back().unwrap(), 4);
+        assert_eq!(
+            deque.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 6, 5, 4]
+        );
+
+        let mut deque = deque_of(1..=5);
+        deque.reverse_back_half();
+        assert_eq!(
+            deque.into_iter().rev().collect::<Vec<_>>(),
+            vec![3, 4, 5, 2, 1]
+        );
+
+        let mut deque = deque_of(1..=3);
+        deque.reverse_back_half();
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1, 3, 2]);
+
+        let mut deque = deque_of([1]);
+        deque.reverse_back_half();
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1]);
+
+        let mut deque = BadSafeDeque::<i32>::new();
+        deque.reverse_back_half();
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn weave() {
+        let mut deque = deque_of(1..=4);
+        let mut other = deque_of(5..=8);
+
+        deque.weave(&mut other);
+        assert!(other.pop_front().is_none());
+        assert!(deque.is_acyclic());
+        assert_eq!(
+            deque.into_iter().collect::<Vec<_>>(),
+            vec![1, 8, 2, 7, 3, 6, 4, 5]
+        );
+
+        let mut deque = deque_of([1]);
+        let mut other = deque_of([2, 3, 4]);
+        deque.weave(&mut other);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1, 4, 3, 2]);
+    }
+
+    #[test]
+    fn swap_ends() {
+        let mut deque = BadSafeDeque::new();
+        deque.swap_ends();
+        assert!(deque.peek_front().is_none());
+
+        deque.push_back(1);
+        deque.swap_ends();
+        assert_eq!(*deque.peek_front().unwrap(), 1);
+        assert_eq!(*deque.peek_back().unwrap(), 1);
+
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_back(4);
+        deque.swap_ends();
+        assert_eq!(*deque.peek_front().unwrap(), 4);
+        assert_eq!(*deque.peek_back().unwrap(), 1);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn rotate() {
+        let mut deque = deque_of(1..=5);
+        deque.rotate_left(2);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![3, 4, 5, 1, 2]);
+
+        let mut deque = deque_of(1..=5);
+        deque.rotate_right(2);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![4, 5, 1, 2, 3]);
+
+        let mut deque = deque_of(1..=5);
+        deque.rotate_left(7);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![3, 4, 5, 1, 2]);
+
+        let mut deque = deque_of(1..=5);
+        deque.rotate_left(5);
+        deque.rotate_right(10);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+        let mut deque = BadSafeDeque::<i32>::new();
+        deque.rotate_left(3);
+        deque.rotate_right(3);
+        assert!(deque.pop_front().is_none());
+    }
+
+    #[test]
+    fn weak_prev() {
+        let mut deque = BadSafeDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_front(0);
+
+        // 強参照は前のノードの next（と head / tail）からだけ
+        let head = deque.head.as_ref().unwrap();
+        let middle = Rc::clone(head.borrow().next.as_ref().unwrap());
+        let tail = deque.tail.as_ref().unwrap();
+        assert_eq!(Rc::strong_count(head), 1);
+        assert_eq!(Rc::strong_count(&middle), 2);
+        assert_eq!(Rc::strong_count(tail), 2);
+
+        let nodes = [
+            Rc::downgrade(head),
+            Rc::downgrade(&middle),
+            Rc::downgrade(tail),
+        ];
+        drop(middle);
+        drop(deque);
+        assert!(nodes.iter().all(|node| node.upgrade().is_none()));
+    }
+
+    #[test]
+    fn append() {
+        let mut deque = BadSafeDeque::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        let mut other = BadSafeDeque::new();
+        other.push_back(3);
+        other.push_back(4);
+
+        deque.append(&mut other);
+        assert_eq!(other.len(), 0);
+        assert!(other.is_empty());
+        assert_eq!(deque.len(), 4);
+        assert!(deque.is_acyclic());
+        assert_eq!(*deque.peek_back().unwrap(), 4);
+
+        let mut empty = BadSafeDeque::new();
+        deque.append(&mut empty);
+        empty.append(&mut deque);
+        assert!(deque.is_empty());
+        assert_eq!(
+            empty.into_iter().rev().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn prepend_all() {
+        let mut deque = BadSafeDeque::new();
+        deque.push_back(3);
+        deque.push_back(4);
+        let mut other = BadSafeDeque::new();
+        other.push_back(1);
+        other.push_back(2);
+
+        deque.prepend_all(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn get() {
+        assert!(BadSafeDeque::<i32>::new().get(0).is_none());
+
+        let deque = deque_of(1..=5);
+        assert_eq!(*deque.get(0).unwrap(), 1);
+        assert_eq!(*deque.get(1).unwrap(), 2);
+        assert_eq!(*deque.get(3).unwrap(), 4);
+        assert_eq!(*deque.get(4).unwrap(), 5);
+        assert!(deque.get(5).is_none());
+
+        let (first, third) = (deque.get(0).unwrap(), deque.get(2).unwrap());
+        assert_eq!((*first, *third), (1, 3));
+    }
+
+    #[test]
+    fn len() {
+        let mut deque = BadSafeDeque::new();
+        assert_eq!(deque.len(), 0);
+
+        deque.push_back(1);
+        deque.push_front(0);
+        deque.push_back(2);
+        assert_eq!(deque.len(), 3);
+
+        deque.pop_back();
+        deque.pop_front();
+        assert_eq!(deque.len(), 1);
+
+        let mut other = BadSafeDeque::new();
+        other.push_back(3);
+        deque.append(&mut other);
+        assert_eq!((deque.len(), other.len()), (2, 0));
+
+        deque.pop_front();
+        deque.pop_front();
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn replace_all() {
+        let mut deque = deque_of([5, 4, 3, 2, 1]);
+
+        deque.replace_all(vec![10, 20, 30]);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(*deque.peek_front().unwrap(), 10);
+        assert_eq!(*deque.peek_back().unwrap(), 30);
+        assert!(deque.is_acyclic());
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![10, 20, 30]);
+
+        let mut deque = deque_of([1]);
+        deque.replace_all(Vec::new());
+        assert!(deque.is_empty());
+        assert!(deque.peek_back().is_none());
+
+        deque.clear();
+        assert_eq!(deque.pop_back(), None);
+    }
+
+    #[test]
+    fn longest_palindromic_run() {
+        assert_eq!(BadSafeDeque::<i32>::new().longest_palindromic_run(), 0);
+        assert_eq!(deque_of([1, 2, 3, 2, 1]).longest_palindromic_run(), 5);
+        assert_eq!(deque_of([1, 2, 2, 1]).longest_palindromic_run(), 4);
+        assert_eq!(
+            deque_of([5, 1, 2, 3, 2, 1, 7, 8]).longest_palindromic_run(),
+            5
+        );
+        assert_eq!(deque_of([9, 4, 4, 6]).longest_palindromic_run(), 2);
+        assert_eq!(deque_of([1, 2, 3, 4]).longest_palindromic_run(), 1);
+    }
+
+    #[test]
+    fn cursor() {
+        let mut deque = deque_of([1, 2, 4]);
+
+        {
+            let mut cursor = deque.cursor_front_mut();
+            assert_eq!(*cursor.current().unwrap(), 1);
+            cursor.move_next();
+            cursor.insert_after(3);
+            cursor.move_next();
+            assert_eq!(*cursor.current().unwrap(), 3);
+            cursor.move_next();
+            cursor.insert_after(5);
+            cursor.move_prev();
+            cursor.move_prev();
+            assert_eq!(*cursor.current().unwrap(), 2);
+        }
+        assert_eq!(deque.len(), 5);
+        assert!(deque.is_acyclic());
+        assert_eq!(*deque.peek_back().unwrap(), 5);
+
+        {
+            let mut cursor = deque.cursor_front_mut();
+            // 先頭を削除するとカーソルは次の要素へ進む
+            assert_eq!(cursor.remove_current(), Some(1));
+            assert_eq!(*cursor.current().unwrap(), 2);
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(3));
+            assert_eq!(*cursor.current().unwrap(), 4);
+            cursor.move_next();
+            // 末尾を削除すると要素を指さない位置になる
+            assert_eq!(cursor.remove_current(), Some(5));
+            assert!(cursor.current().is_none());
+            assert_eq!(cursor.remove_current(), None);
+            cursor.move_prev();
+            assert_eq!(*cursor.current().unwrap(), 4);
+        }
+        assert_eq!(deque.len(), 2);
+        assert_eq!(*deque.peek_front().unwrap(), 2);
+        assert_eq!(*deque.peek_back().unwrap(), 4);
+
+        {
+            let mut cursor = deque.cursor_front_mut();
+            cursor.move_prev();
+            assert!(cursor.current().is_none());
+            cursor.insert_after(0);
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(0));
+            assert_eq!(cursor.remove_current(), Some(2));
+            assert_eq!(cursor.remove_current(), Some(4));
+        }
+        assert!(deque.is_empty());
+        assert!(deque.peek_front().is_none());
+        assert!(deque.peek_back().is_none());
+
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.cursor_front_mut().insert_after(9);
+        assert_eq!(deque.into_iter().rev().collect::<Vec<_>>(), vec![2, 9, 1]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn rolling_hashes() {
+        let deque = deque_of([1, 2, 3, 1, 2, 3, 4]);
+
+        let hashes = deque.rolling_hashes(3);
+        assert_eq!(hashes.len(), 5);
+        // [1,2,3] は 0 番目と 3 番目に現れる
+        assert_eq!(hashes[0], hashes[3]);
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[3], hashes[4]);
+
+        // ずらしながら求めた値と、窓ごとに求め直した値が一致する
+        let window = deque_of([2, 3, 4]);
+        assert_eq!(window.rolling_hashes(3), vec![hashes[4]]);
+
+        assert_eq!(deque.rolling_hashes(7).len(), 1);
+        assert!(deque.rolling_hashes(0).is_empty());
+        assert!(deque.rolling_hashes(8).is_empty());
+    }
+
+    #[test]
+    fn front_back() {
+        let mut deque = BadSafeDeque::new();
+        assert!(deque.front().is_none());
+        assert!(deque.back().is_none());
+
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(*deque.front().unwrap(), *deque.peek_front().unwrap());
+        assert_eq!(*deque.back().unwrap(), *deque.peek_back().unwrap());
+        assert_eq!(*deque.front().unwrap(), 1);
+        assert_eq!(*deque.back().unwrap(), 3);
+
+        *deque.front_mut().unwrap() = 10;
+        *deque.back_mut().unwrap() = 30;
+        assert_eq!(*deque.peek_front().unwrap(), 10);
+        assert_eq!(*deque.peek_back().unwrap(), 30);
+    }
+
+    #[test]
+    fn pop_if() {
+        let mut deque = BadSafeDeque::new();
+        assert_eq!(deque.pop_front_if(|_| true), None);
+
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+
+        assert_eq!(deque.pop_front_if(|&x| x > 1), None);
+        assert_eq!(deque.pop_back_if(|&x| x < 3), None);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(*deque.peek_front().unwrap(), 1);
+        assert_eq!(*deque.peek_back().unwrap(), 3);
+
+        assert_eq!(deque.pop_front_if(|&x| x == 1), Some(1));
+        assert_eq!(deque.pop_back_if(|&x| x == 3), Some(3));
+        assert_eq!(deque.len(), 1);
+        assert_eq!(*deque.peek_front().unwrap(), 2);
+        assert_eq!(*deque.peek_back().unwrap(), 2);
+    }
+
+    #[test]
+    fn extend_front_back() {
+        let mut deque = BadSafeDeque::new();
+        deque.extend_back(vec![4, 5, 6]);
+        deque.extend_front(vec![3, 2, 1]);
+        assert_eq!(deque.len(), 6);
+        assert_eq!(
+            deque.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+
+        let mut deque = BadSafeDeque::new();
+        deque.extend_front(vec![1, 2, 3]);
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn eq() {
+        let mut front_pushed = BadSafeDeque::new();
+        front_pushed.extend_front(vec![3, 2, 1]);
+        let mut back_pushed = BadSafeDeque::new();
+        back_pushed.extend_back(vec![1, 2, 3]);
+        assert!(front_pushed == back_pushed);
+
+        back_pushed.push_back(4);
+        assert!(front_pushed != back_pushed);
+
+        front_pushed.push_back(5);
+        assert!(front_pushed != back_pushed);
+
+        assert!(BadSafeDeque::<i32>::new() == BadSafeDeque::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn eq_with_outstanding_borrow_mut() {
+        let mut a = BadSafeDeque::new();
+        a.push_back(1);
+        let mut b = BadSafeDeque::new();
+        b.push_back(1);
+        let _guard = a.peek_front_mut();
+        let _ = a == b;
+    }
+
+    #[test]
+    fn iter_back() {
+        let mut deque = BadSafeDeque::new();
+        deque.push_front(1);
+        deque.push_front(2);
+        deque.push_front(3);
+
+        assert_eq!(
+            deque.iter_back().map(|x| *x).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        // 借りている間も他の要素を読める
+        let mut iter = deque.iter_back();
+        let last = iter.next().unwrap();
+        assert_eq!(*deque.peek_back().unwrap(), *last);
+        assert_eq!(*iter.next().unwrap(), 2);
+        drop(last);
+        assert_eq!(deque.len(), 3);
+
+        assert!(BadSafeDeque::<i32>::new().iter_back().next().is_none());
+    }
+
+    #[test]
+    fn iter_exact_size() {
+        let mut deque = BadSafeDeque::new();
+        deque.extend_back(vec![1, 2, 3]);
+
+        {
+            let mut iter = deque.iter_back();
+            assert_eq!(iter.len(), deque.len());
+            assert_eq!(iter.size_hint(), (3, Some(3)));
+            iter.next();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+        }
+
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 1);
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
 }
@@ -1,4 +1,5 @@
-use std::mem;
+use alloc::boxed::Box;
+use core::mem;
 
 pub struct BadStack {
     head: Link,
@@ -38,6 +39,12 @@ impl BadStack {
     }
 }
 
+impl Default for BadStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for BadStack {
     fn drop(&mut self) {
         let mut cur_link = mem::replace(&mut self.head, Link::Empty);
@@ -0,0 +1,88 @@
+use crate::ok_stack::OkStack;
+
+// 深さが cap を超えないように、push のたびに一番下の要素を追い出す OkStack
+pub struct DepthCappedStack<T> {
+    stack: OkStack<T>,
+    cap: usize,
+    len: usize,
+}
+
+impl<T> DepthCappedStack<T> {
+    pub fn new(cap: usize) -> Self {
+        DepthCappedStack {
+            stack: OkStack::new(),
+            cap,
+            len: 0,
+        }
+    }
+
+    // cap を超えたら一番下（最も古い）要素を取り除いて返す
+    pub fn push(&mut self, elem: T) -> Option<T> {
+        self.stack.push(elem);
+        if self.len < self.cap {
+            self.len += 1;
+            return None;
+        }
+        // cap 個より下を切り離すと、追い出す要素だけが残る
+        self.stack.split_off(self.cap).pop()
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let elem = self.stack.pop()?;
+        self.len -= 1;
+        Some(elem)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.stack.peek()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+}
+
+#[cfg(test)]
+mod test_depth_capped_stack {
+    use super::DepthCappedStack;
+
+    #[test]
+    fn evict() {
+        let mut stack = DepthCappedStack::new(3);
+        assert_eq!(stack.push(1), None);
+        assert_eq!(stack.push(2), None);
+        assert_eq!(stack.push(3), None);
+        assert_eq!(stack.len(), 3);
+
+        assert_eq!(stack.push(4), Some(1));
+        assert_eq!(stack.push(5), Some(2));
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack.peek(), Some(&5));
+
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.push(6), None);
+        assert_eq!(stack.push(7), Some(3));
+
+        assert_eq!(stack.pop(), Some(7));
+        assert_eq!(stack.pop(), Some(6));
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn zero_cap() {
+        let mut stack = DepthCappedStack::new(0);
+        assert_eq!(stack.push(1), Some(1));
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+    }
+}
@@ -1,4 +1,14 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+// no_std でもテストでは std のスレッドやコレクションを使う
+#[cfg(test)]
+extern crate std;
+
+pub mod arc_stack;
 pub mod bad_safe_deque;
 pub mod bad_stack;
+pub mod depth_capped_stack;
+pub mod max_tracking_stack;
 pub mod ok_stack;
 pub mod persistent_stack;
@@ -0,0 +1,82 @@
+use crate::ok_stack::OkStack;
+
+// OkStack に「その時点の最大値」を積んだ補助スタックを添えて、
+// push / pop / max をすべて O(1) にしたもの
+pub struct MaxTrackingStack<T> {
+    stack: OkStack<T>,
+    maxima: OkStack<T>,
+}
+
+impl<T: Ord + Clone> MaxTrackingStack<T> {
+    pub fn new() -> Self {
+        MaxTrackingStack {
+            stack: OkStack::new(),
+            maxima: OkStack::new(),
+        }
+    }
+
+    pub fn push(&mut self, elem: T) {
+        // 現在の最大値以上なら補助スタックにも積む（同じ値も積んでおかないと pop で消えてしまう）
+        if self.maxima.peek().is_none_or(|max| elem >= *max) {
+            self.maxima.push(elem.clone());
+        }
+        self.stack.push(elem);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let elem = self.stack.pop()?;
+        if self.maxima.peek() == Some(&elem) {
+            self.maxima.pop();
+        }
+        Some(elem)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.stack.peek()
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.maxima.peek()
+    }
+}
+
+impl<T: Ord + Clone> Default for MaxTrackingStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_max_tracking_stack {
+    use super::MaxTrackingStack;
+
+    #[test]
+    fn max() {
+        let mut stack = MaxTrackingStack::new();
+        assert_eq!(stack.max(), None);
+
+        stack.push(3);
+        assert_eq!(stack.max(), Some(&3));
+        stack.push(1);
+        assert_eq!(stack.max(), Some(&3));
+        stack.push(5);
+        assert_eq!(stack.max(), Some(&5));
+        stack.push(5);
+        assert_eq!(stack.max(), Some(&5));
+        stack.push(2);
+        assert_eq!(stack.max(), Some(&5));
+
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.max(), Some(&5));
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.max(), Some(&5));
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.max(), Some(&3));
+        assert_eq!(stack.peek(), Some(&1));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.max(), Some(&3));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.max(), None);
+        assert_eq!(stack.pop(), None);
+    }
+}
@@ -1,5 +1,18 @@
+use alloc::{alloc::Layout, boxed::Box, collections::BinaryHeap, vec::Vec};
+use core::{
+    cmp::{Ordering, Reverse},
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ptr,
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
 pub struct OkStack<T> {
     head: Link<T>,
+    // pop したノードの領域を次の push で使い回すための置き場（elem は未初期化）
+    pool: Vec<Box<MaybeUninit<Node<T>>>>,
+    pool_cap: usize,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
@@ -11,21 +24,84 @@ struct Node<T> {
 
 impl<T> OkStack<T> {
     pub fn new() -> Self {
-        OkStack { head: None }
+        OkStack {
+            head: None,
+            pool: Vec::new(),
+            pool_cap: 0,
+        }
+    }
+
+    // n 個のノードをあらかじめ確保しておき、pop したノードも n 個までは解放せずに再利用する
+    pub fn with_pool_capacity(n: usize) -> Self {
+        let mut stack = OkStack::new();
+        stack.pool = (0..n).map(|_| Box::new_uninit()).collect();
+        stack.pool_cap = n;
+        stack
+    }
+
+    // 置き場に溜めたノードを解放する（容量はそのままなので、以降の pop でまた溜まる）
+    pub fn clear_pool(&mut self) {
+        self.pool.clear();
     }
 
     pub fn push(&mut self, elem: T) {
-        let new_node = Box::new(Node {
+        let node = Node {
             elem,
             next: self.head.take(),
-        });
+        };
+        let new_node = match self.pool.pop() {
+            Some(mut slot) => {
+                slot.write(node);
+                // SAFETY: 直前の write で初期化済み
+                unsafe { slot.assume_init() }
+            }
+            None => Box::new(node),
+        };
         self.head = Some(new_node);
     }
 
+    // メモリ確保に失敗したときは abort せず、要素をそのまま Err で返す
+    // 置き場にノードがあればそれを使い、なければグローバルアロケータを直接呼ぶ
+    // （Box::try_new は安定版にないため）
+    pub fn try_push(&mut self, elem: T) -> Result<(), T> {
+        let mut slot = match self.pool.pop() {
+            Some(slot) => slot,
+            None => {
+                // SAFETY: Node<T> は next を持つのでサイズが 0 になることはない
+                let ptr = unsafe { alloc::alloc::alloc(Layout::new::<Node<T>>()) }
+                    as *mut MaybeUninit<Node<T>>;
+                if ptr.is_null() {
+                    return Err(elem);
+                }
+                // SAFETY: ptr は Node<T> のレイアウトで確保したばかりの未初期化の領域なので、
+                // MaybeUninit の Box として所有させてよい
+                unsafe { Box::from_raw(ptr) }
+            }
+        };
+        slot.write(Node {
+            elem,
+            next: self.head.take(),
+        });
+        // SAFETY: 直前の write で初期化済み
+        self.head = Some(unsafe { slot.assume_init() });
+        Ok(())
+    }
+
     pub fn pop(&mut self) -> Option<T> {
-        self.head.take().map(|node| {
-            self.head = node.next;
-            node.elem
+        self.head.take().map(|mut node| {
+            self.head = node.next.take();
+            if self.pool.len() >= self.pool_cap {
+                return node.elem;
+            }
+            let raw = Box::into_raw(node);
+            // SAFETY: elem を読み出したあとは未初期化として扱い、二重に drop しない
+            // next は take 済みの None なので drop しなくてよい
+            unsafe {
+                let elem = ptr::read(&raw const (*raw).elem);
+                self.pool
+                    .push(Box::from_raw(raw.cast::<MaybeUninit<Node<T>>>()));
+                elem
+            }
         })
     }
 
@@ -36,21 +112,533 @@ impl<T> OkStack<T> {
     pub fn peek_mut(&mut self) -> Option<&mut T> {
         self.head.as_mut().map(|node| &mut node.elem)
     }
-}
 
-impl<T> Drop for OkStack<T> {
-    fn drop(&mut self) {
+    // head があれば f を適用して true を返す
+    pub fn modify_top<F: FnOnce(&mut T)>(&mut self, f: F) -> bool {
+        self.peek_mut().map(f).is_some()
+    }
+
+    // head の値を elem と入れ替えて古い値を返す（空のときは何もしない）
+    pub fn replace_top(&mut self, elem: T) -> Option<T> {
+        self.peek_mut().map(|top| core::mem::replace(top, elem))
+    }
+
+    pub fn clear(&mut self) {
         let mut cur_link = self.head.take();
         while let Some(mut boxed_node) = cur_link {
             cur_link = boxed_node.next.take();
         }
     }
+
+    // v[0] が head になる（順に push すると順序が逆になるのを避ける）
+    pub fn from_vec(v: Vec<T>) -> Self {
+        let mut stack = OkStack::new();
+        for elem in v.into_iter().rev() {
+            stack.push(elem);
+        }
+        stack
+    }
+
+    // 位置 i の要素を f(i) にする（位置 0 が head、後ろの位置から積む）
+    pub fn from_fn<F: FnMut(usize) -> T>(n: usize, mut f: F) -> Self {
+        let mut stack = OkStack::new();
+        for i in (0..n).rev() {
+            stack.push(f(i));
+        }
+        stack
+    }
+
+    // head から順に並べた Vec にする（from_vec の逆）
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
+    }
+
+    // Vec::swap_remove と同様に、index 番目に head の要素を移してから pop する（順序は保たない）
+    pub fn swap_remove(&mut self, index: usize) -> Option<T> {
+        if index > 0 {
+            let Node { elem, next } = &mut **self.head.as_mut()?;
+            let mut cur_link = next;
+            for _ in 1..index {
+                cur_link = &mut cur_link.as_mut()?.next;
+            }
+            core::mem::swap(elem, &mut cur_link.as_mut()?.elem);
+        }
+        self.pop()
+    }
+
+    // 深さ [start, end) の要素を複製した新しいスタックを返す（end は長さで打ち切る）
+    pub fn slice(&self, start: usize, end: usize) -> OkStack<T>
+    where
+        T: Clone,
+    {
+        OkStack::from_vec(self.iter().take(end).skip(start).cloned().collect())
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    // pred を満たす最初の要素を top にするのに必要な pop の回数（実際には pop しない）
+    pub fn pops_until<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    pub fn find<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<&T> {
+        self.iter().find(|elem| pred(elem))
+    }
+
+    // 両方のスタックを要素の集合とみなしたジャッカード係数（どちらも空なら 1.0）
+    #[cfg(feature = "std")]
+    pub fn jaccard(a: &OkStack<T>, b: &OkStack<T>) -> f64
+    where
+        T: Eq + Hash,
+    {
+        let a: HashSet<&T> = a.iter().collect();
+        let b: HashSet<&T> = b.iter().collect();
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 1.0;
+        }
+        a.intersection(&b).count() as f64 / union as f64
+    }
+
+    // 上から読んだ要素列どうしのレーベンシュタイン距離
+    pub fn levenshtein(a: &OkStack<T>, b: &OkStack<T>) -> usize
+    where
+        T: PartialEq,
+    {
+        let a: Vec<&T> = a.iter().collect();
+        let b: Vec<&T> = b.iter().collect();
+        // dp[j] は a の先頭 i 個と b の先頭 j 個の距離
+        let mut dp: Vec<usize> = (0..=b.len()).collect();
+        for (i, x) in a.iter().enumerate() {
+            let mut diagonal = dp[0];
+            dp[0] = i + 1;
+            for (j, y) in b.iter().enumerate() {
+                let substitution = diagonal + usize::from(x != y);
+                diagonal = dp[j + 1];
+                dp[j + 1] = substitution.min(dp[j] + 1).min(diagonal + 1);
+            }
+        }
+        dp[b.len()]
+    }
+
+    // 大きい順に n 個の要素を返す
+    // （大きさ n の最小ヒープで、それまでの上位 n 個だけを持ちながら走査する）
+    pub fn top_n(&self, n: usize) -> Vec<&T>
+    where
+        T: Ord,
+    {
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+        for elem in self.iter() {
+            heap.push(Reverse(elem));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(elem)| elem)
+            .collect()
+    }
+
+    // 要素数の半分より多く現れる要素を返す
+    #[cfg(feature = "std")]
+    pub fn majority(&self) -> Option<&T>
+    where
+        T: Eq + Hash,
+    {
+        let mut counts = HashMap::new();
+        let mut len = 0;
+        for elem in self.iter() {
+            *counts.entry(elem).or_insert(0) += 1;
+            len += 1;
+        }
+        counts
+            .into_iter()
+            .find(|&(_, count)| count > len / 2)
+            .map(|(elem, _)| elem)
+    }
+
+    // f が false を返した要素のノードを外し、残りを元の順序のまま繋ぎ直す
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut cur_link = self.head.take();
+        let mut tail = &mut self.head;
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            if f(&node.elem) {
+                tail = &mut tail.insert(node).next;
+            }
+        }
+    }
+
+    // 連続する同じ要素のノードを外して一つにまとめる
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut cur_link = self.head.as_mut();
+        while let Some(node) = cur_link {
+            while node
+                .next
+                .as_ref()
+                .is_some_and(|next| next.elem == node.elem)
+            {
+                let mut duplicate = node.next.take().unwrap();
+                node.next = duplicate.next.take();
+            }
+            cur_link = node.next.as_mut();
+        }
+    }
+
+    // value と等しい要素のノードを先頭側に集める（どちらの側も元の順序を保つ）
+    pub fn bring_to_front(&mut self, value: &T)
+    where
+        T: PartialEq,
+    {
+        let mut matched = None;
+        let mut matched_tail = &mut matched;
+        let mut rest = None;
+        let mut rest_tail = &mut rest;
+        let mut cur_link = self.head.take();
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            if node.elem == *value {
+                matched_tail = &mut matched_tail.insert(node).next;
+            } else {
+                rest_tail = &mut rest_tail.insert(node).next;
+            }
+        }
+        *matched_tail = rest;
+        self.head = matched;
+    }
+
+    // link から辿った末尾の None を返す
+    fn last_link(mut cur_link: &mut Link<T>) -> &mut Link<T> {
+        while let Some(node) = cur_link {
+            cur_link = &mut node.next;
+        }
+        cur_link
+    }
+
+    // self の末尾まで辿り、other のノードをまるごと繋げる
+    pub fn append(&mut self, other: &mut OkStack<T>) {
+        *Self::last_link(&mut self.head) = other.head.take();
+    }
+
+    // 順に末尾へ繋げていく（直前に繋げたスタックの末尾から辿るので全体で O(要素数)）
+    pub fn concat<I: IntoIterator<Item = OkStack<T>>>(iter: I) -> OkStack<T> {
+        let mut stack = OkStack::new();
+        let mut tail = &mut stack.head;
+        for mut other in iter {
+            *tail = other.head.take();
+            tail = Self::last_link(tail);
+        }
+        stack
+    }
+
+    // 先頭から at 個を self に残し、それより下を新しいスタックとして切り離す
+    pub fn split_off(&mut self, at: usize) -> OkStack<T> {
+        let mut cur_link = &mut self.head;
+        for _ in 0..at {
+            match cur_link {
+                Some(node) => cur_link = &mut node.next,
+                None => break,
+            }
+        }
+        let mut rest = OkStack::new();
+        rest.head = cur_link.take();
+        rest
+    }
+
+    // 先頭 len 個だけ残す（切り離した残りは Drop で反復的に解放される）
+    pub fn truncate(&mut self, len: usize) {
+        self.split_off(len);
+    }
+
+    // 上から連続する区間ごとに k 個のスタックへ分ける（先頭の len % k 個は 1 つ多くなる）
+    pub fn split_k(self, k: usize) -> Vec<OkStack<T>> {
+        assert!(k > 0, "split_k requires k > 0");
+        let len = self.iter().count();
+        let mut chunks = Vec::with_capacity(k);
+        let mut rest = self;
+        for i in 0..k {
+            let size = len / k + usize::from(i < len % k);
+            let tail = rest.split_off(size);
+            chunks.push(core::mem::replace(&mut rest, tail));
+        }
+        chunks
+    }
+
+    // 上から chunk_len 個ずつのスタックに分ける（最後だけ短くなることがある）
+    // chunk_len が 0 だと分けられないので panic する
+    pub fn split_into_chunks(self, chunk_len: usize) -> Vec<OkStack<T>> {
+        assert!(chunk_len > 0, "split_into_chunks requires chunk_len > 0");
+        let mut chunks = Vec::new();
+        let mut rest = self;
+        while rest.head.is_some() {
+            let tail = rest.split_off(chunk_len);
+            chunks.push(core::mem::replace(&mut rest, tail));
+        }
+        chunks
+    }
+
+    // index 番目のノードを外して先頭に付け直す（LRU の move-to-front）
+    pub fn promote(&mut self, index: usize) {
+        assert!(
+            self.iter().nth(index).is_some(),
+            "promote index out of range"
+        );
+        if index == 0 {
+            return;
+        }
+
+        let mut node = {
+            let mut prev = self.head.as_mut().unwrap();
+            for _ in 1..index {
+                prev = prev.next.as_mut().unwrap();
+            }
+            let mut node = prev.next.take().unwrap();
+            prev.next = node.next.take();
+            node
+        };
+        node.next = self.head.take();
+        self.head = Some(node);
+    }
+
+    // head から昇順に並んだ a と b のノードを、小さい方から順に繋ぎ直して一つにする
+    pub fn merge_sorted(mut a: OkStack<T>, mut b: OkStack<T>) -> OkStack<T>
+    where
+        T: Ord,
+    {
+        let mut merged = OkStack::new();
+        let mut tail = &mut merged.head;
+        let (mut a_link, mut b_link) = (a.head.take(), b.head.take());
+        loop {
+            let from = match (&a_link, &b_link) {
+                (Some(x), Some(y)) if x.elem <= y.elem => &mut a_link,
+                (Some(_), Some(_)) => &mut b_link,
+                // 片方が尽きたら残りをまとめて繋ぐ
+                _ => {
+                    *tail = a_link.or(b_link);
+                    break;
+                }
+            };
+            let mut node = from.take().unwrap();
+            *from = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        merged
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    // ノードを Vec に外して安定ソートし、その順に繋ぎ直す（要素は動かさない）
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let mut nodes = Vec::new();
+        let mut cur_link = self.head.take();
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            nodes.push(node);
+        }
+
+        nodes.sort_by(|a, b| compare(&a.elem, &b.elem));
+
+        for mut node in nodes.into_iter().rev() {
+            node.next = self.head.take();
+            self.head = Some(node);
+        }
+    }
+
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut cur_link = self.head.take();
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    // 上から順に重みを足していき、max を超える手前で新しいスタックに切り替える
+    // （単独で max を超える要素はそれだけでひとつのスタックになる）
+    pub fn split_by_weight<F: Fn(&T) -> u64>(mut self, weight: F, max: u64) -> Vec<OkStack<T>> {
+        let mut chunks = Vec::new();
+        let mut chunk = OkStack::new();
+        let mut sum: u64 = 0;
+        while let Some(elem) = self.pop() {
+            let w = weight(&elem);
+            if chunk.peek().is_some() && sum.saturating_add(w) > max {
+                chunk.reverse();
+                chunks.push(core::mem::take(&mut chunk));
+                sum = 0;
+            }
+            sum = sum.saturating_add(w);
+            chunk.push(elem);
+        }
+        if chunk.peek().is_some() {
+            chunk.reverse();
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    // i 番目と j 番目のノードそのものを next の付け替えで入れ替える
+    pub fn swap_nodes(&mut self, i: usize, j: usize) {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        assert!(
+            self.iter().nth(j).is_some(),
+            "swap_nodes index out of range"
+        );
+
+        // 先頭から j 番目までのノードを切り離して並べる
+        let mut nodes = Vec::with_capacity(j + 1);
+        let mut rest = self.head.take();
+        while nodes.len() <= j {
+            let mut node = rest.unwrap();
+            rest = node.next.take();
+            nodes.push(node);
+        }
+
+        nodes.swap(i, j);
+
+        // 後ろから順に繋ぎ直す
+        for mut node in nodes.into_iter().rev() {
+            node.next = rest;
+            rest = Some(node);
+        }
+        self.head = rest;
+    }
+
+    // 上から見て、各要素からひとつ下の要素を引いた差を並べる
+    // 差を T で返すので、符号なし整数で下の要素の方が大きいとオーバーフローする
+    // （debug ビルドでは panic、release ビルドではラップした値になる）
+    pub fn deltas(&self) -> Vec<T>
+    where
+        T: Copy + core::ops::Sub<Output = T>,
+    {
+        self.pairwise()
+            .map(|(&upper, &lower)| upper - lower)
+            .collect()
+    }
+
+    // 上から読んで隣り合う要素の差がすべて等しいか（要素が 2 個未満なら true）
+    // 符号なし整数でもオーバーフローしないよう、差は (増減の向き, 大きい方 - 小さい方) で比べる
+    pub fn is_arithmetic(&self) -> bool
+    where
+        T: Copy + PartialOrd + core::ops::Sub<Output = T>,
+    {
+        let mut diffs = self.pairwise().map(|(&upper, &lower)| {
+            if upper >= lower {
+                (true, upper - lower)
+            } else {
+                (false, lower - upper)
+            }
+        });
+        match diffs.next() {
+            Some(first) => diffs.all(|diff| diff == first),
+            None => true,
+        }
+    }
+
+    // 株価スパン問題: 上から読んで、自身を含め直前に連続する自身以下の要素数（単調スタックで O(n)）
+    pub fn spans(&self) -> Vec<usize>
+    where
+        T: PartialOrd,
+    {
+        let mut spans = Vec::new();
+        let mut monotonic: Vec<(usize, &T)> = Vec::new();
+        for (i, elem) in self.iter().enumerate() {
+            while monotonic.last().is_some_and(|&(_, top)| top <= elem) {
+                monotonic.pop();
+            }
+            spans.push(monotonic.last().map_or(i + 1, |&(j, _)| i - j));
+            monotonic.push((i, elem));
+        }
+        spans
+    }
+
+    // 上から隣り合う要素の組 (i 番目, i + 1 番目) を順に返す
+    pub fn pairwise(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.iter().zip(self.iter().skip(1))
+    }
+
+    // Ok の値を順に push し、最初の Err でそれまでに積んだスタックを捨てて返す
+    pub fn try_from_iter<E, I: IntoIterator<Item = Result<T, E>>>(iter: I) -> Result<Self, E> {
+        let mut stack = OkStack::new();
+        for elem in iter {
+            stack.push(elem?);
+        }
+        Ok(stack)
+    }
+}
+
+impl<T> OkStack<Option<T>> {
+    // None を取り除き、Some の中身だけを上から同じ順序で積み直す
+    pub fn flatten_options(self) -> OkStack<T> {
+        OkStack::from_vec(self.into_iter().flatten().collect())
+    }
+}
+
+impl<T: PartialEq> PartialEq for OkStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for OkStack<T> {}
+
+// 要素を head から順にハッシュし、最後に要素数を加える
+impl<T: Hash> Hash for OkStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0;
+        for elem in self.iter() {
+            elem.hash(state);
+            len += 1;
+        }
+        state.write_usize(len);
+    }
+}
+
+impl<T> Default for OkStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for OkStack<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
 }
 
 pub struct IntoIter<T>(OkStack<T>);
 
-impl<T> OkStack<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
+impl<T> IntoIterator for OkStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
         IntoIter(self)
     }
 }
@@ -68,7 +656,7 @@ pub struct Iter<'a, T> {
 }
 
 impl<T> OkStack<T> {
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
         }
@@ -86,6 +674,34 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
+// 単方向リストなので、最初に全要素の参照を Vec に集めてから後ろ向きに返す
+// （作成時に O(n) の時間とメモリがかかる）
+pub struct IterRev<'a, T> {
+    elems: Vec<&'a T>,
+}
+
+impl<T> OkStack<T> {
+    pub fn iter_rev(&self) -> IterRev<'_, T> {
+        IterRev {
+            elems: self.iter().collect(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterRev<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elems.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.elems.len(), Some(self.elems.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IterRev<'_, T> {}
+
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
@@ -112,6 +728,12 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 #[cfg(test)]
 mod test_ok_stack {
     use super::OkStack;
+    use alloc::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use std::collections::HashSet;
 
     #[test]
     fn basics() {
@@ -197,4 +819,725 @@ mod test_ok_stack {
         assert_eq!(iter.next(), Some(&mut 4));
         assert_eq!(iter.next(), Some(&mut 2));
     }
+
+    #[test]
+    fn try_from_iter() {
+        let stack = OkStack::try_from_iter(vec![Ok::<_, String>(1), Ok(2), Ok(3)]).unwrap();
+        let mut iter = stack.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+
+        let result = OkStack::try_from_iter(vec![
+            Ok(1),
+            Err("bad".to_string()),
+            Ok(3),
+            Err("worse".to_string()),
+        ]);
+        assert_eq!(result.err(), Some("bad".to_string()));
+
+        let empty = OkStack::<i32>::try_from_iter(Vec::<Result<i32, String>>::new()).unwrap();
+        assert_eq!(empty.peek(), None);
+    }
+
+    #[test]
+    fn flatten_options() {
+        let mut stack = OkStack::new();
+        stack.push(Some(1));
+        stack.push(None);
+        stack.push(Some(2));
+        stack.push(None);
+        stack.push(Some(3));
+
+        let stack = stack.flatten_options();
+        let mut iter = stack.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn get() {
+        let mut stack = OkStack::new();
+        assert_eq!(stack.get(0), None);
+
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        assert_eq!(stack.get(0), Some(&3));
+        assert_eq!(stack.get(1), Some(&2));
+        assert_eq!(stack.get(2), Some(&1));
+        assert_eq!(stack.get(3), None);
+
+        if let Some(v) = stack.get_mut(1) {
+            *v = 42;
+        }
+        assert_eq!(stack.get(1), Some(&42));
+        assert_eq!(stack.get_mut(3), None);
+    }
+
+    #[test]
+    fn swap_nodes() {
+        let mut stack = OkStack::from_vec(vec![5, 4, 3, 2, 1]);
+
+        // 値ではなくノードが移動していることを要素のアドレスで確かめる
+        let addr = stack.get(1).unwrap() as *const i32;
+        stack.swap_nodes(1, 3);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&5, &2, &3, &4, &1]);
+        assert_eq!(stack.get(3).unwrap() as *const i32, addr);
+
+        stack.swap_nodes(2, 3);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&5, &2, &4, &3, &1]);
+
+        stack.swap_nodes(4, 0);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2, &4, &3, &5]);
+
+        stack.swap_nodes(0, 1);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &1, &4, &3, &5]);
+
+        stack.swap_nodes(2, 2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &1, &4, &3, &5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_nodes_out_of_range() {
+        let mut stack = OkStack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.swap_nodes(0, 2);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut stack = OkStack::<i32>::new();
+        stack.reverse();
+        assert_eq!(stack.peek(), None);
+
+        stack.push(1);
+        stack.reverse();
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1]);
+
+        stack.push(2);
+        stack.push(3);
+        stack.reverse();
+        let mut iter = stack.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn deltas() {
+        let mut stack = OkStack::new();
+        assert!(stack.deltas().is_empty());
+
+        stack.push(1);
+        assert!(stack.deltas().is_empty());
+
+        stack.push(4);
+        stack.push(6);
+        stack.push(5);
+        assert_eq!(stack.deltas(), vec![-1, 2, 3]);
+    }
+
+    #[test]
+    fn append() {
+        let mut stack = OkStack::new();
+        stack.push(3);
+        stack.push(4);
+        let mut other = OkStack::new();
+        other.push(1);
+        other.push(2);
+
+        stack.append(&mut other);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+        assert_eq!(other.peek(), None);
+
+        let mut empty = OkStack::new();
+        empty.append(&mut stack);
+        assert_eq!(empty.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+        assert_eq!(stack.peek(), None);
+
+        empty.append(&mut stack);
+        assert_eq!(empty.iter().count(), 4);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut stack = OkStack::from_vec(vec![4, 3, 2, 1]);
+        let tail = stack.split_off(2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&4, &3]);
+        assert_eq!(tail.iter().collect::<Vec<_>>(), vec![&2, &1]);
+
+        let mut stack = OkStack::from_vec(vec![4, 3, 2, 1]);
+        let tail = stack.split_off(0);
+        assert_eq!(stack.peek(), None);
+        assert_eq!(tail.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+
+        let mut stack = OkStack::from_vec(vec![4, 3, 2, 1]);
+        let tail = stack.split_off(4);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+        assert_eq!(tail.peek(), None);
+
+        let mut stack = OkStack::from_vec(vec![4, 3, 2, 1]);
+        let tail = stack.split_off(10);
+        assert_eq!(stack.iter().count(), 4);
+        assert_eq!(tail.peek(), None);
+    }
+
+    #[test]
+    fn contains_and_find() {
+        let mut stack = OkStack::new();
+        assert!(!stack.contains(&1));
+        assert_eq!(stack.find(|_| true), None);
+
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        assert!(stack.contains(&2));
+        assert!(!stack.contains(&4));
+        assert_eq!(stack.find(|&x| x % 2 == 0), Some(&2));
+        assert_eq!(stack.find(|&x| x < 3), Some(&2));
+        assert_eq!(stack.find(|&x| x > 3), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn jaccard() {
+        assert_eq!(
+            OkStack::<i32>::jaccard(&OkStack::from_vec(vec![]), &OkStack::from_vec(vec![])),
+            1.0
+        );
+        assert_eq!(
+            OkStack::jaccard(
+                &OkStack::from_vec(vec![1, 2, 2, 3]),
+                &OkStack::from_vec(vec![3, 1, 2])
+            ),
+            1.0
+        );
+        assert_eq!(
+            OkStack::jaccard(
+                &OkStack::from_vec(vec![1, 2]),
+                &OkStack::from_vec(vec![3, 4])
+            ),
+            0.0
+        );
+        assert_eq!(
+            OkStack::jaccard(
+                &OkStack::from_vec(vec![1, 2, 3]),
+                &OkStack::from_vec(vec![2, 3, 4])
+            ),
+            0.5
+        );
+        assert_eq!(
+            OkStack::jaccard(&OkStack::from_vec(vec![1]), &OkStack::from_vec(vec![])),
+            0.0
+        );
+    }
+
+    #[test]
+    fn clear() {
+        let mut stack = OkStack::new();
+        stack.clear();
+        assert_eq!(stack.pop(), None);
+
+        for i in 0..100 {
+            stack.push(i);
+        }
+        stack.clear();
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.iter().count(), 0);
+
+        stack.push(1);
+        assert_eq!(stack.pop(), Some(1));
+    }
+
+    #[test]
+    fn retain() {
+        let mut stack = OkStack::from_vec(vec![5, 4, 3, 2, 1]);
+
+        stack.retain(|&x| x % 2 == 0);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&4, &2]);
+        assert_eq!(stack.iter().count(), 2);
+
+        stack.push(6);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&6, &4, &2]);
+
+        stack.retain(|_| false);
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn slice() {
+        let stack = OkStack::from_vec(vec![5, 4, 3, 2, 1]);
+
+        let full = stack.slice(0, 5);
+        assert_eq!(full.iter().collect::<Vec<_>>(), vec![&5, &4, &3, &2, &1]);
+
+        let window = stack.slice(1, 4);
+        assert_eq!(window.iter().collect::<Vec<_>>(), vec![&4, &3, &2]);
+
+        let prefix = stack.slice(0, 2);
+        assert_eq!(prefix.iter().collect::<Vec<_>>(), vec![&5, &4]);
+
+        let clamped = stack.slice(3, 100);
+        assert_eq!(clamped.iter().collect::<Vec<_>>(), vec![&2, &1]);
+
+        assert_eq!(stack.slice(5, 10).peek(), None);
+        assert_eq!(stack.slice(3, 2).peek(), None);
+        assert_eq!(stack.iter().count(), 5);
+    }
+
+    #[test]
+    fn promote() {
+        let mut stack = OkStack::from_vec(vec![4, 3, 2, 1]);
+
+        stack.promote(2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &4, &3, &1]);
+
+        stack.promote(3);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2, &4, &3]);
+
+        stack.promote(0);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2, &4, &3]);
+
+        stack.promote(1);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &1, &4, &3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn promote_out_of_range() {
+        let mut stack = OkStack::new();
+        stack.push(1);
+        stack.promote(1);
+    }
+
+    #[test]
+    fn hash() {
+        assert!(OkStack::from_vec(vec![1, 2, 3]) == OkStack::from_vec(vec![1, 2, 3]));
+        assert!(OkStack::from_vec(vec![1, 2, 3]) != OkStack::from_vec(vec![1, 2]));
+
+        let mut set = HashSet::new();
+        set.insert(OkStack::from_vec(vec![1, 2, 3]));
+        set.insert(OkStack::from_vec(vec![1, 2, 3]));
+        assert_eq!(set.len(), 1);
+
+        set.insert(OkStack::from_vec(vec![3, 2, 1]));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn split_by_weight() {
+        let collect = |chunks: Vec<OkStack<u64>>| {
+            chunks
+                .iter()
+                .map(|chunk| chunk.iter().copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        let chunks = OkStack::from_vec(vec![2, 3, 5, 1, 4]).split_by_weight(|&x| x, 5);
+        assert_eq!(collect(chunks), vec![vec![2, 3], vec![5], vec![1, 4]]);
+
+        let chunks = OkStack::from_vec(vec![1, 2, 3, 4]).split_by_weight(|&x| x, 3);
+        assert_eq!(collect(chunks), vec![vec![1, 2], vec![3], vec![4]]);
+
+        let chunks = OkStack::from_vec(vec![1, 10, 2]).split_by_weight(|&x| x, 5);
+        assert_eq!(collect(chunks), vec![vec![1], vec![10], vec![2]]);
+
+        assert!(OkStack::new().split_by_weight(|&x: &u64| x, 5).is_empty());
+    }
+
+    #[test]
+    fn vec_conversion() {
+        let mut stack = OkStack::from_vec(vec![1, 2, 3]);
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), None);
+
+        let v = vec![5, 4, 3, 2, 1];
+        assert_eq!(OkStack::from_vec(v.clone()).into_vec(), v);
+        assert!(OkStack::<i32>::from_vec(Vec::new()).into_vec().is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn majority() {
+        assert_eq!(OkStack::<i32>::new().majority(), None);
+        assert_eq!(OkStack::from_vec(vec![1, 2, 1, 3, 1]).majority(), Some(&1));
+        assert_eq!(OkStack::from_vec(vec![1, 2, 1, 2]).majority(), None);
+        assert_eq!(OkStack::from_vec(vec![7]).majority(), Some(&7));
+    }
+
+    #[test]
+    fn levenshtein() {
+        let distance = |a: Vec<char>, b: Vec<char>| {
+            OkStack::levenshtein(&OkStack::from_vec(a), &OkStack::from_vec(b))
+        };
+
+        assert_eq!(distance(vec![], vec![]), 0);
+        assert_eq!(distance(vec!['a', 'b', 'c'], vec!['a', 'b', 'c']), 0);
+        assert_eq!(distance(vec!['a', 'c'], vec!['a', 'b', 'c']), 1);
+        assert_eq!(distance(vec!['a', 'b', 'c'], vec!['a', 'x', 'c']), 1);
+        assert_eq!(distance(vec!['a', 'b', 'c'], vec!['x', 'y', 'z']), 3);
+        assert_eq!(
+            distance("kitten".chars().collect(), "sitting".chars().collect()),
+            3
+        );
+        assert_eq!(distance(vec![], vec!['a', 'b']), 2);
+    }
+
+    #[test]
+    fn try_push() {
+        let mut stack = OkStack::new();
+        assert_eq!(stack.try_push(1), Ok(()));
+        assert_eq!(stack.try_push(2), Ok(()));
+        stack.push(3);
+        assert_eq!(stack.try_push(4), Ok(()));
+
+        assert_eq!(stack.into_vec(), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn pairwise() {
+        let stack = OkStack::from_vec(vec![1, 2, 3, 4]);
+        let mut pairs = stack.pairwise();
+        assert_eq!(pairs.next(), Some((&1, &2)));
+        assert_eq!(pairs.next(), Some((&2, &3)));
+        assert_eq!(pairs.next(), Some((&3, &4)));
+        assert_eq!(pairs.next(), None);
+
+        assert_eq!(OkStack::from_vec(vec![1]).pairwise().count(), 0);
+        assert_eq!(OkStack::<i32>::new().pairwise().count(), 0);
+    }
+
+    #[test]
+    fn iter_rev() {
+        let mut stack = OkStack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        let mut iter = stack.iter_rev();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(OkStack::<i32>::new().iter_rev().next(), None);
+    }
+
+    #[test]
+    fn top_n() {
+        let stack = OkStack::from_vec(vec![3, 9, 1, 7, 5]);
+        assert_eq!(stack.top_n(2), vec![&9, &7]);
+        assert_eq!(stack.top_n(5), vec![&9, &7, &5, &3, &1]);
+        assert_eq!(stack.top_n(10), vec![&9, &7, &5, &3, &1]);
+        assert!(stack.top_n(0).is_empty());
+        assert!(OkStack::<i32>::new().top_n(3).is_empty());
+    }
+
+    #[test]
+    fn merge_sorted() {
+        let merged = OkStack::merge_sorted(
+            OkStack::from_vec(vec![1, 3, 5]),
+            OkStack::from_vec(vec![2, 4, 6]),
+        );
+        assert_eq!(merged.into_vec(), vec![1, 2, 3, 4, 5, 6]);
+
+        let merged = OkStack::merge_sorted(
+            OkStack::from_vec(vec![1, 2, 8, 9]),
+            OkStack::from_vec(vec![2, 3]),
+        );
+        assert_eq!(merged.into_vec(), vec![1, 2, 2, 3, 8, 9]);
+
+        let merged = OkStack::merge_sorted(OkStack::new(), OkStack::from_vec(vec![1, 2]));
+        assert_eq!(merged.into_vec(), vec![1, 2]);
+
+        let merged = OkStack::merge_sorted(OkStack::from_vec(vec![1, 2]), OkStack::new());
+        assert_eq!(merged.into_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn is_arithmetic() {
+        assert!(OkStack::from_vec(vec![1, 3, 5, 7]).is_arithmetic());
+        assert!(OkStack::from_vec(vec![9, 6, 3, 0, -3]).is_arithmetic());
+        assert!(OkStack::from_vec(vec![2, 2, 2]).is_arithmetic());
+        assert!(!OkStack::from_vec(vec![1, 2, 4, 8]).is_arithmetic());
+        assert!(!OkStack::from_vec(vec![1, 3, 5, 8]).is_arithmetic());
+        assert!(OkStack::from_vec(vec![4, 10]).is_arithmetic());
+        assert!(OkStack::from_vec(vec![4]).is_arithmetic());
+        assert!(OkStack::<i32>::new().is_arithmetic());
+
+        // 符号なし整数で上から増えていく場合も引き算がオーバーフローしない
+        assert!(OkStack::from_vec(vec![1u32, 3, 5]).is_arithmetic());
+        assert!(OkStack::from_vec(vec![5u32, 3, 1]).is_arithmetic());
+        assert!(!OkStack::from_vec(vec![1u32, 3, 1]).is_arithmetic());
+        assert!(!OkStack::from_vec(vec![0u8, 255, 254]).is_arithmetic());
+    }
+
+    #[test]
+    fn sort() {
+        let mut stack = OkStack::from_vec(vec![3, 1, 2]);
+        stack.sort();
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        stack.sort_by(|a, b| b.cmp(a));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        // 安定ソートなので、キーが同じ要素は元の順序を保つ
+        let mut stack = OkStack::from_vec(vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+        stack.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            stack.into_vec(),
+            vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+
+        let mut stack = OkStack::<i32>::new();
+        stack.sort();
+        assert_eq!(stack.peek(), None);
+
+        let mut stack = OkStack::from_vec(vec![1]);
+        stack.sort();
+        assert_eq!(stack.into_vec(), vec![1]);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut stack = OkStack::from_vec(vec![1, 1, 2, 3, 3, 3, 1]);
+        stack.dedup();
+        assert_eq!(stack.into_vec(), vec![1, 2, 3, 1]);
+
+        let mut stack = OkStack::from_vec(vec![7, 7, 7, 7]);
+        stack.dedup();
+        assert_eq!(stack.into_vec(), vec![7]);
+
+        let mut stack = OkStack::from_vec(vec![1, 2, 3]);
+        stack.dedup();
+        assert_eq!(stack.into_vec(), vec![1, 2, 3]);
+
+        let mut stack = OkStack::<i32>::new();
+        stack.dedup();
+        assert_eq!(stack.peek(), None);
+    }
+
+    #[test]
+    fn bring_to_front() {
+        let mut stack = OkStack::from_vec(vec![(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (1, 'e')]);
+        let addr = stack.get(2).unwrap() as *const (i32, char);
+        stack.bring_to_front(&(1, 'c'));
+        assert_eq!(stack.get(0).unwrap() as *const (i32, char), addr);
+
+        let mut stack = OkStack::from_vec(vec![3, 1, 2, 1, 4, 1]);
+        stack.bring_to_front(&1);
+        assert_eq!(stack.into_vec(), vec![1, 1, 1, 3, 2, 4]);
+
+        let mut stack = OkStack::from_vec(vec![5, 5, 5]);
+        stack.bring_to_front(&5);
+        assert_eq!(stack.into_vec(), vec![5, 5, 5]);
+
+        let mut stack = OkStack::from_vec(vec![1, 2, 3]);
+        stack.bring_to_front(&9);
+        assert_eq!(stack.into_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn concat() {
+        let stack = OkStack::concat(vec![
+            OkStack::from_vec(vec![2, 1]),
+            OkStack::new(),
+            OkStack::from_vec(vec![4, 3]),
+            OkStack::from_vec(vec![5]),
+        ]);
+        assert_eq!(stack.into_vec(), vec![2, 1, 4, 3, 5]);
+
+        assert_eq!(OkStack::<i32>::concat(Vec::new()).peek(), None);
+    }
+
+    #[test]
+    fn split_k() {
+        let collect = |chunks: Vec<OkStack<i32>>| {
+            chunks
+                .into_iter()
+                .map(OkStack::into_vec)
+                .collect::<Vec<_>>()
+        };
+
+        let chunks = OkStack::from_vec(vec![1, 2, 3, 4, 5, 6]).split_k(3);
+        assert_eq!(collect(chunks), vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+
+        let chunks = OkStack::from_vec(vec![1, 2, 3, 4, 5, 6, 7]).split_k(3);
+        assert_eq!(collect(chunks), vec![vec![1, 2, 3], vec![4, 5], vec![6, 7]]);
+
+        let chunks = OkStack::from_vec(vec![1, 2]).split_k(4);
+        assert_eq!(collect(chunks), vec![vec![1], vec![2], vec![], vec![]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_k_zero() {
+        OkStack::from_vec(vec![1]).split_k(0);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut stack = OkStack::from_vec(vec![5, 4, 3, 2, 1]);
+        stack.truncate(2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&5, &4]);
+
+        stack.truncate(10);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&5, &4]);
+
+        stack.truncate(0);
+        assert_eq!(stack.peek(), None);
+    }
+
+    #[test]
+    fn spans() {
+        let stack = OkStack::from_vec(vec![100, 80, 60, 70, 60, 75, 85]);
+        assert_eq!(stack.spans(), vec![1, 1, 1, 2, 1, 4, 6]);
+
+        assert_eq!(OkStack::<i32>::new().spans(), vec![]);
+    }
+
+    #[test]
+    fn pops_until() {
+        let stack = OkStack::from_vec(vec![1, 2, 3, 4]);
+        assert_eq!(stack.pops_until(|&x| x == 1), Some(0));
+        assert_eq!(stack.pops_until(|&x| x > 2), Some(2));
+        assert_eq!(stack.pops_until(|&x| x == 4), Some(3));
+        assert_eq!(stack.pops_until(|&x| x > 4), None);
+        assert_eq!(stack.iter().count(), 4);
+    }
+
+    #[test]
+    fn from_fn() {
+        let stack = OkStack::from_fn(4, |i| i * i);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&0, &1, &4, &9]);
+
+        assert_eq!(OkStack::from_fn(0, |i| i).peek(), None);
+    }
+
+    #[test]
+    fn swap_remove() {
+        let mut stack = OkStack::from_vec(vec![1, 2, 3, 4, 5]);
+        assert_eq!(stack.swap_remove(0), Some(1));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &3, &4, &5]);
+
+        assert_eq!(stack.swap_remove(2), Some(4));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &5]);
+
+        assert_eq!(stack.swap_remove(2), Some(5));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &3]);
+
+        assert_eq!(stack.swap_remove(2), None);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &3]);
+        assert_eq!(OkStack::<i32>::new().swap_remove(0), None);
+    }
+
+    #[test]
+    fn pool() {
+        let mut stack = OkStack::with_pool_capacity(2);
+        assert_eq!(stack.pool.len(), 2);
+
+        for round in 0..100 {
+            stack.push(round);
+            stack.push(round + 1);
+            assert!(stack.pool.is_empty());
+            stack.push(round + 2);
+            assert_eq!(stack.pop(), Some(round + 2));
+            assert_eq!(stack.pop(), Some(round + 1));
+            assert_eq!(stack.pop(), Some(round));
+            assert_eq!(stack.pop(), None);
+            // 容量を超えた分は解放される
+            assert_eq!(stack.pool.len(), 2);
+        }
+
+        // pop したノードの領域がそのまま次の push で使われる
+        stack.push(1);
+        let addr = stack.peek().unwrap() as *const i32;
+        stack.pop();
+        stack.push(2);
+        assert_eq!(stack.peek().unwrap() as *const i32, addr);
+
+        // try_push も置き場のノードを先に使う
+        stack.pop();
+        assert_eq!(stack.try_push(4), Ok(()));
+        assert_eq!(stack.peek().unwrap() as *const i32, addr);
+        assert_eq!(stack.pop(), Some(4));
+
+        stack.clear_pool();
+        assert!(stack.pool.is_empty());
+        stack.push(3);
+        stack.pop();
+        assert_eq!(stack.pool.len(), 1);
+
+        // 置き場に戻したノードの elem は二重に drop されない
+        let mut stack = OkStack::with_pool_capacity(1);
+        stack.push(String::from("x"));
+        stack.push(String::from("y"));
+        assert_eq!(stack.pop().as_deref(), Some("y"));
+        assert_eq!(stack.pop().as_deref(), Some("x"));
+        stack.push(String::from("z"));
+        drop(stack);
+
+        // 既定では置き場を持たない
+        let mut stack = OkStack::new();
+        stack.push(String::from("a"));
+        assert_eq!(stack.pop().as_deref(), Some("a"));
+        assert!(stack.pool.is_empty());
+    }
+
+    #[test]
+    fn split_into_chunks() {
+        let chunks = OkStack::from_vec(vec![5, 4, 3, 2, 1]).split_into_chunks(2);
+        assert_eq!(
+            chunks
+                .into_iter()
+                .map(OkStack::into_vec)
+                .collect::<Vec<_>>(),
+            vec![vec![5, 4], vec![3, 2], vec![1]]
+        );
+
+        assert!(OkStack::<i32>::new().split_into_chunks(3).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_into_chunks_zero() {
+        OkStack::from_vec(vec![1]).split_into_chunks(0);
+    }
+
+    #[test]
+    fn modify_replace_top() {
+        let mut stack = OkStack::new();
+        assert!(!stack.modify_top(|x| *x += 1));
+        assert_eq!(stack.replace_top(1), None);
+        assert_eq!(stack.peek(), None);
+
+        stack.push(1);
+        stack.push(2);
+        assert!(stack.modify_top(|x| *x *= 10));
+        assert_eq!(stack.peek(), Some(&20));
+
+        assert_eq!(stack.replace_top(3), Some(20));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &1]);
+    }
+
+    #[test]
+    fn iter_rev_exact_size() {
+        let stack = OkStack::from_vec(vec![1, 2, 3]);
+        let mut iter = stack.iter_rev();
+        assert_eq!(iter.len(), stack.iter().count());
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+    }
 }
@@ -1,7 +1,11 @@
-use std::rc::Rc;
+use alloc::{rc::Rc, vec::Vec};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
 pub struct PersistentStack<T> {
     head: Link<T>,
+    len: usize,
 }
 
 type Link<T> = Option<Rc<Node<T>>>;
@@ -13,15 +17,24 @@ struct Node<T> {
 
 impl<T> PersistentStack<T> {
     pub fn new() -> Self {
-        PersistentStack { head: None }
+        PersistentStack { head: None, len: 0 }
+    }
+
+    // slice[0] が head になる（prepend は順序を逆にするので後ろから積む）
+    pub fn from_slice(slice: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        slice.iter().rev().cloned().collect()
     }
 
     pub fn prepend(&self, elem: T) -> Self {
         PersistentStack {
             head: Some(Rc::new(Node {
                 elem,
-                next: self.head.as_ref().map(|node| Rc::clone(node)),
+                next: self.head.as_ref().map(Rc::clone),
             })),
+            len: self.len + 1,
         }
     }
 
@@ -30,17 +43,219 @@ impl<T> PersistentStack<T> {
             head: self
                 .head
                 .as_ref()
-                .and_then(|node| node.next.as_ref().map(|node| Rc::clone(&node))),
+                .and_then(|node| node.next.as_ref().map(Rc::clone)),
+            len: self.len.saturating_sub(1),
         }
     }
 
     pub fn head(&self) -> Option<&T> {
         self.head.as_ref().map(|node| &node.elem)
     }
+
+    // 0 が最後に prepend した要素
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    pub fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    pub fn count<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        self.iter().filter(|elem| pred(elem)).count()
+    }
+
+    // head から tail の順に畳み込む
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // head から順に prepend し直すので、要素の並びが逆になる
+    pub fn reversed(&self) -> PersistentStack<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    // 他のスタックと共有されていない先頭部分だけを逆順にし、共有部分はそのまま繋ぐ
+    // strong_count が 2 以上のノードが見つかったら、そこから先はすべて共有部分とみなす
+    pub fn reverse_unshared(&self) -> PersistentStack<T>
+    where
+        T: Clone,
+    {
+        let mut prefix = Vec::new();
+        let mut rest = self.head.as_ref();
+        while let Some(node) = rest {
+            if Rc::strong_count(node) > 1 {
+                break;
+            }
+            prefix.push(node.elem.clone());
+            rest = node.next.as_ref();
+        }
+        let shared = PersistentStack {
+            head: rest.map(Rc::clone),
+            len: self.len - prefix.len(),
+        };
+        // head から順に prepend すると先頭部分だけが逆順になる
+        prefix
+            .into_iter()
+            .fold(shared, |stack, elem| stack.prepend(elem))
+    }
+
+    // head から tail の並びの後ろに、同じ要素を逆順で繋げた新しいスタックを作る
+    // 結果は回文なので、FromIterator で順序が逆になってもそのまま積めばよい
+    pub fn fold_over(&self) -> PersistentStack<T>
+    where
+        T: Clone,
+    {
+        let elems: Vec<&T> = self.iter().collect();
+        elems
+            .iter()
+            .chain(elems.iter().rev())
+            .map(|&elem| elem.clone())
+            .collect()
+    }
+
+    // 先頭 index 個を複製した新しいスタックと、index 番目以降のノードを共有するスタックに分ける
+    pub fn split_at(&self, index: usize) -> (PersistentStack<T>, PersistentStack<T>)
+    where
+        T: Clone,
+    {
+        let mut prefix = Vec::new();
+        let mut rest = self.head.as_ref();
+        while prefix.len() < index {
+            match rest {
+                Some(node) => {
+                    prefix.push(node.elem.clone());
+                    rest = node.next.as_ref();
+                }
+                None => break,
+            }
+        }
+        let suffix = PersistentStack {
+            head: rest.map(Rc::clone),
+            len: self.len - prefix.len(),
+        };
+        (prefix.into_iter().rev().collect(), suffix)
+    }
+
+    // head から昇順に並んだ 2 つのスタックを複製しながらマージする（a, b はそのまま残る）
+    pub fn merge_sorted(a: &PersistentStack<T>, b: &PersistentStack<T>) -> PersistentStack<T>
+    where
+        T: Ord + Clone,
+    {
+        let mut merged = Vec::with_capacity(a.len + b.len);
+        let (mut a_iter, mut b_iter) = (a.iter().peekable(), b.iter().peekable());
+        loop {
+            let next = match (a_iter.peek(), b_iter.peek()) {
+                (Some(x), Some(y)) if x <= y => a_iter.next(),
+                (Some(_), Some(_)) => b_iter.next(),
+                // 片方が尽きたら残りをまとめて並べる
+                _ => {
+                    merged.extend(a_iter.chain(b_iter));
+                    break;
+                }
+            };
+            merged.extend(next);
+        }
+        merged.into_iter().rev().cloned().collect()
+    }
+
+    // pred を満たす要素だけを複製した新しいスタックを、head から同じ順序で作る
+    pub fn filter<P: FnMut(&T) -> bool>(&self, mut pred: P) -> PersistentStack<T>
+    where
+        T: Clone,
+    {
+        let elems: Vec<&T> = self.iter().filter(|elem| pred(elem)).collect();
+        elems.into_iter().rev().cloned().collect()
+    }
+
+    // head から順に対にした新しいスタックを作る（短い方の長さで打ち切る）
+    pub fn zip<U>(&self, other: &PersistentStack<U>) -> PersistentStack<(T, U)>
+    where
+        T: Clone,
+        U: Clone,
+    {
+        let pairs: Vec<(&T, &U)> = self.iter().zip(other.iter()).collect();
+        pairs
+            .into_iter()
+            .rev()
+            .map(|(a, b)| (a.clone(), b.clone()))
+            .collect()
+    }
+
+    // 各要素に f を適用した新しいスタックを、head から同じ順序で作る
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> PersistentStack<U> {
+        let elems: Vec<U> = self.iter().map(f).collect();
+        elems.into_iter().rev().collect()
+    }
+
+    // head から各ノードの Rc::strong_count を並べる
+    // 共有が始まるノードで 2 以上になる（その先は直前のノードからの参照だけなので 1 に戻る）
+    pub fn strong_counts(&self) -> Vec<usize> {
+        let mut counts = Vec::with_capacity(self.len);
+        let mut cur = self.head.as_ref();
+        while let Some(node) = cur {
+            counts.push(Rc::strong_count(node));
+            cur = node.next.as_ref();
+        }
+        counts
+    }
+
+    // 同じ要素が連続する区間の長さごとに、その区間がいくつあるかを数える
+    #[cfg(feature = "std")]
+    pub fn run_length_histogram(&self) -> HashMap<usize, usize>
+    where
+        T: PartialEq,
+    {
+        let mut histogram = HashMap::new();
+        let mut iter = self.iter().peekable();
+        while let Some(elem) = iter.next() {
+            let mut run = 1;
+            while iter.next_if(|next| *next == elem).is_some() {
+                run += 1;
+            }
+            *histogram.entry(run).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    // head から size 個ずつ区切り、それぞれを同じ順序の新しいスタックとして返す
+    pub fn chunks(&self, size: usize) -> Vec<PersistentStack<T>>
+    where
+        T: Clone,
+    {
+        if size == 0 {
+            return Vec::new();
+        }
+        let elems: Vec<&T> = self.iter().collect();
+        elems
+            .chunks(size)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(PersistentStack::new(), |stack, elem| {
+                        stack.prepend((*elem).clone())
+                    })
+            })
+            .collect()
+    }
 }
 
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    // 残りの要素数（len を持っているので最初から分かる）
+    len: usize,
 }
 
 impl<T> PersistentStack<T> {
@@ -49,6 +264,7 @@ impl<T> PersistentStack<T> {
             // next: self.head.as_ref().map(|node| &**node),
             // next: self.head.as_ref().map::<&Node<T>, _>(|node| &node),
             next: self.head.as_deref(),
+            len: self.len,
         }
     }
 }
@@ -59,9 +275,68 @@ impl<'a, T> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
             self.next = node.next.as_deref();
+            self.len -= 1;
             &node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for PersistentStack<T> {
+    fn clone(&self) -> Self {
+        PersistentStack {
+            head: self.head.as_ref().map(Rc::clone),
+            len: self.len,
+        }
+    }
+}
+
+// 要素を順に prepend するので、最後に取り出した要素が head になる
+// （(1..=3).collect() の head は 3）
+impl<T> FromIterator<T> for PersistentStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = PersistentStack::new();
+        for elem in iter {
+            stack = stack.prepend(elem);
+        }
+        stack
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+        // 同じノードを共有していれば中身を比べるまでもない
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) if Rc::ptr_eq(a, b) => true,
+            _ => self.iter().eq(other.iter()),
+        }
+    }
+}
+
+impl<T: Eq> Eq for PersistentStack<T> {}
+
+// 要素を head から順にハッシュし、最後に長さを加える
+impl<T: Hash> Hash for PersistentStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+        state.write_usize(self.len);
+    }
+}
+
+impl<T> Default for PersistentStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> Drop for PersistentStack<T> {
@@ -80,6 +355,17 @@ impl<T> Drop for PersistentStack<T> {
 #[cfg(test)]
 mod test_persistent_stack {
     use super::PersistentStack;
+    use alloc::{string::String, vec, vec::Vec};
+    use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+    // drop されるたびにカウンタを増やす要素
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
 
     #[test]
     fn basics() {
@@ -108,4 +394,417 @@ mod test_persistent_stack {
         assert_eq!(iter.next(), Some(&2));
         assert_eq!(iter.next(), Some(&1));
     }
+
+    #[test]
+    fn len() {
+        let stack = PersistentStack::new();
+        assert_eq!(stack.len(), 0);
+        assert!(stack.is_empty());
+
+        let stack = stack.prepend(1).prepend(2).prepend(3);
+        assert_eq!(stack.len(), 3);
+        assert!(!stack.is_empty());
+
+        let stack = stack.tail();
+        assert_eq!(stack.len(), 2);
+
+        let stack = stack.tail().tail().tail();
+        assert_eq!(stack.len(), 0);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn clone() {
+        let stack = PersistentStack::new().prepend(1).prepend(2);
+        let other = stack.clone();
+        assert_eq!(other.head(), Some(&2));
+        assert_eq!(other.len(), 2);
+
+        let stack = stack.prepend(3);
+        assert_eq!(stack.head(), Some(&3));
+        assert_eq!(stack.len(), 3);
+
+        let mut iter = other.iter();
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(other.len(), 2);
+    }
+
+    #[test]
+    fn eq() {
+        let a = PersistentStack::new().prepend(1).prepend(2).prepend(3);
+        let b = PersistentStack::new().prepend(1).prepend(2).prepend(3);
+        assert!(a == b);
+
+        let c = a.clone();
+        assert!(a == c);
+        assert!(a.tail() == b.tail());
+
+        assert!(a != a.tail());
+        assert!(a != PersistentStack::new().prepend(9).prepend(2).prepend(3));
+        assert!(PersistentStack::<i32>::new() == PersistentStack::new());
+
+        // NaN は要素ごとに比べると等しくならないので、ptr_eq で打ち切ったときだけ true になる
+        let nan = PersistentStack::new().prepend(f64::NAN);
+        assert!(nan == nan.clone());
+        assert!(nan != PersistentStack::new().prepend(f64::NAN));
+    }
+
+    #[test]
+    fn from_iter() {
+        let stack: PersistentStack<_> = (1..=3).collect();
+        assert_eq!(stack.head(), Some(&3));
+        assert_eq!(stack.len(), 3);
+
+        let mut iter = stack.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn chunks() {
+        let stack: PersistentStack<_> = (1..=7).collect();
+        let chunks = stack.chunks(3);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].iter().collect::<Vec<_>>(), vec![&7, &6, &5]);
+        assert_eq!(chunks[1].iter().collect::<Vec<_>>(), vec![&4, &3, &2]);
+        assert_eq!(chunks[2].iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(chunks[2].len(), 1);
+
+        assert!(stack.chunks(0).is_empty());
+        assert!(PersistentStack::<i32>::new().chunks(3).is_empty());
+    }
+
+    #[test]
+    fn from_slice() {
+        let stack = PersistentStack::from_slice(&[1, 2, 3]);
+        assert_eq!(stack.head(), Some(&1));
+        assert_eq!(stack.len(), 3);
+
+        let mut iter = stack.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+
+        assert!(PersistentStack::<i32>::from_slice(&[]).is_empty());
+    }
+
+    #[test]
+    fn map() {
+        let stack = PersistentStack::new().prepend(1).prepend(2).prepend(3);
+        let mapped = stack.map(|x| x * 10);
+        assert_eq!(mapped.len(), 3);
+
+        let mut iter = mapped.iter();
+        assert_eq!(iter.next(), Some(&30));
+        assert_eq!(iter.next(), Some(&20));
+        assert_eq!(iter.next(), Some(&10));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(stack.head(), Some(&3));
+    }
+
+    #[test]
+    fn get() {
+        let stack = PersistentStack::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(stack.get(0), Some(&3));
+        assert_eq!(stack.get(1), Some(&2));
+        assert_eq!(stack.get(2), Some(&1));
+        assert_eq!(stack.get(3), None);
+        assert_eq!(PersistentStack::<i32>::new().get(0), None);
+    }
+
+    #[test]
+    fn hash() {
+        let a = PersistentStack::new().prepend(1).prepend(2).prepend(3);
+        let b = PersistentStack::new().prepend(1).prepend(2).prepend(3);
+
+        let mut set = HashSet::new();
+        set.insert(a.clone());
+        set.insert(b);
+        assert_eq!(set.len(), 1);
+
+        set.insert(a.tail());
+        assert_eq!(set.len(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn run_length_histogram() {
+        assert!(PersistentStack::<i32>::new()
+            .run_length_histogram()
+            .is_empty());
+
+        let stack = PersistentStack::from_slice(&[1, 2, 2, 3, 3, 3, 4, 1, 1]);
+        let histogram = stack.run_length_histogram();
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram[&1], 2);
+        assert_eq!(histogram[&2], 2);
+        assert_eq!(histogram[&3], 1);
+    }
+
+    #[test]
+    fn drop_shared_tail() {
+        for drop_a_first in [true, false] {
+            let dropped = Rc::new(Cell::new(0));
+            let counter = || DropCounter(Rc::clone(&dropped));
+
+            let base = PersistentStack::new()
+                .prepend(counter())
+                .prepend(counter())
+                .prepend(counter());
+            let a = base.prepend(counter());
+            let b = base.prepend(counter()).prepend(counter());
+            drop(base);
+            assert_eq!(dropped.get(), 0);
+
+            let (first, second, first_only) = if drop_a_first { (a, b, 1) } else { (b, a, 2) };
+            drop(first);
+            assert_eq!(dropped.get(), first_only);
+
+            drop(second);
+            assert_eq!(dropped.get(), 6);
+        }
+    }
+
+    #[test]
+    fn drop_long_shared_tail() {
+        let base: PersistentStack<_> = (0..100_000).collect();
+        let a = base.prepend(-1);
+        let b = base.tail().prepend(-2);
+        drop(base);
+        drop(a);
+        assert_eq!(b.len(), 100_000);
+        assert_eq!(b.iter().count(), 100_000);
+    }
+
+    #[test]
+    fn position_and_count() {
+        let stack = PersistentStack::from_slice(&[4, 3, 2, 1]);
+        assert_eq!(stack.position(|&x| x == 4), Some(0));
+        assert_eq!(stack.position(|&x| x < 3), Some(2));
+        assert_eq!(stack.position(|&x| x > 4), None);
+
+        assert_eq!(stack.count(|&x| x % 2 == 0), 2);
+        assert_eq!(stack.count(|&x| x == 4), 1);
+        assert_eq!(stack.count(|&x| x > 4), 0);
+    }
+
+    #[test]
+    fn len_with_shared_tail() {
+        let base = PersistentStack::new().prepend(1).prepend(2);
+        let a = base.prepend(3).prepend(4);
+        let b = base.prepend(5);
+        assert_eq!((base.len(), a.len(), b.len()), (2, 4, 3));
+
+        let a_tail = a.tail().tail();
+        assert_eq!(a_tail.len(), 2);
+        assert!(a_tail == base);
+
+        drop(base);
+        assert_eq!(b.tail().len(), 2);
+        assert_eq!(b.tail().tail().tail().tail().len(), 0);
+        assert_eq!(a.len(), a.iter().count());
+    }
+
+    #[test]
+    fn strong_counts() {
+        let base = PersistentStack::new().prepend(1).prepend(2);
+        assert_eq!(base.strong_counts(), vec![1, 1]);
+
+        let a = base.prepend(3).prepend(4);
+        let b = base.prepend(5);
+        assert_eq!(a.strong_counts(), vec![1, 1, 3, 1]);
+        assert_eq!(b.strong_counts(), vec![1, 3, 1]);
+
+        drop(base);
+        assert_eq!(a.strong_counts(), vec![1, 1, 2, 1]);
+
+        let c = a.clone();
+        assert_eq!(c.strong_counts(), vec![2, 1, 2, 1]);
+        assert!(PersistentStack::<i32>::new().strong_counts().is_empty());
+    }
+
+    #[test]
+    fn split_at() {
+        let stack = PersistentStack::from_slice(&[4, 3, 2, 1]);
+
+        let (front, back) = stack.split_at(2);
+        assert_eq!(front.iter().collect::<Vec<_>>(), vec![&4, &3]);
+        assert_eq!(back.iter().collect::<Vec<_>>(), vec![&2, &1]);
+        assert_eq!((front.len(), back.len()), (2, 2));
+        // 後半は元のスタックのノードをそのまま共有する
+        let shared = stack.tail().tail();
+        assert!(Rc::ptr_eq(
+            back.head.as_ref().unwrap(),
+            shared.head.as_ref().unwrap()
+        ));
+
+        let (front, back) = stack.split_at(0);
+        assert!(front.is_empty());
+        assert!(back == stack);
+
+        let (front, back) = stack.split_at(4);
+        assert!(front == stack);
+        assert!(back.is_empty());
+
+        let (front, back) = stack.split_at(10);
+        assert!(front == stack);
+        assert!(back.is_empty());
+        assert_eq!(stack.len(), 4);
+    }
+
+    #[test]
+    fn reversed() {
+        let stack = PersistentStack::from_slice(&[3, 2, 1]);
+        let reversed = stack.reversed();
+        assert_eq!(reversed.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(reversed.len(), 3);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert!(PersistentStack::<i32>::new().reversed().is_empty());
+    }
+
+    #[test]
+    fn reverse_unshared() {
+        let base = PersistentStack::new().prepend(1).prepend(2);
+        let fork = base.prepend(3).prepend(4).prepend(5);
+
+        let reversed = fork.reverse_unshared();
+        assert_eq!(
+            reversed.iter().collect::<Vec<_>>(),
+            vec![&3, &4, &5, &2, &1]
+        );
+        assert_eq!(reversed.len(), 5);
+        let shared = reversed.tail().tail().tail();
+        assert!(Rc::ptr_eq(
+            shared.head.as_ref().unwrap(),
+            base.head.as_ref().unwrap()
+        ));
+
+        // 元のスタックはそのまま
+        assert_eq!(fork.iter().collect::<Vec<_>>(), vec![&5, &4, &3, &2, &1]);
+
+        // ハンドルが共有されていれば何も反転しない
+        let handle = fork.clone();
+        assert!(fork.reverse_unshared() == handle);
+
+        // どこも共有されていなければ全体が反転する
+        let alone = PersistentStack::from_slice(&[1, 2, 3]);
+        assert_eq!(
+            alone.reverse_unshared().iter().collect::<Vec<_>>(),
+            vec![&3, &2, &1]
+        );
+    }
+
+    #[test]
+    fn fold_over() {
+        let stack = PersistentStack::from_slice(&[1, 2, 3]);
+        let folded = stack.fold_over();
+        assert_eq!(
+            folded.iter().collect::<Vec<_>>(),
+            vec![&1, &2, &3, &3, &2, &1]
+        );
+        assert_eq!(folded.len(), 6);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        // 新しく確保したノードなので元のスタックとは共有しない
+        assert_eq!(stack.strong_counts(), vec![1, 1, 1]);
+
+        assert!(PersistentStack::<i32>::new().fold_over().is_empty());
+    }
+
+    #[test]
+    fn merge_sorted() {
+        let a = PersistentStack::from_slice(&[1, 3, 5]);
+        let b = PersistentStack::from_slice(&[2, 4, 6]);
+        let merged = PersistentStack::merge_sorted(&a, &b);
+        assert_eq!(
+            merged.iter().collect::<Vec<_>>(),
+            vec![&1, &2, &3, &4, &5, &6]
+        );
+        assert_eq!(merged.len(), 6);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![&1, &3, &5]);
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+
+        let c = PersistentStack::from_slice(&[1, 2, 8, 9]);
+        let d = PersistentStack::from_slice(&[2, 3]);
+        assert_eq!(
+            PersistentStack::merge_sorted(&c, &d)
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![&1, &2, &2, &3, &8, &9]
+        );
+
+        let empty = PersistentStack::new();
+        assert!(PersistentStack::merge_sorted(&empty, &a) == a);
+        assert!(PersistentStack::merge_sorted(&a, &empty) == a);
+    }
+
+    #[test]
+    fn fold() {
+        let stack = PersistentStack::from_slice(&[3, 2, 1]);
+        assert_eq!(stack.fold(0, |acc, x| acc + x), 6);
+
+        let words = PersistentStack::from_slice(&["a", "b", "c"]);
+        assert_eq!(
+            words.fold(String::new(), |mut acc, word| {
+                acc.push_str(word);
+                acc
+            }),
+            "abc"
+        );
+
+        assert_eq!(PersistentStack::<i32>::new().fold(7, |acc, x| acc + x), 7);
+    }
+
+    #[test]
+    fn iter_exact_size() {
+        let stack = PersistentStack::from_slice(&[3, 2, 1]);
+        let mut iter = stack.iter();
+        assert_eq!(iter.len(), stack.len());
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+
+        // tail で共有部分だけを見るときも len が合う
+        let tail = stack.tail();
+        assert_eq!(tail.iter().len(), 2);
+        assert_eq!(
+            PersistentStack::<i32>::new().iter().size_hint(),
+            (0, Some(0))
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let stack = PersistentStack::from_slice(&[5, 4, 3, 2, 1]);
+        let evens = stack.filter(|x| x % 2 == 0);
+        assert_eq!(evens.iter().collect::<Vec<_>>(), vec![&4, &2]);
+        assert_eq!(evens.len(), 2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&5, &4, &3, &2, &1]);
+        assert_eq!(stack.len(), 5);
+
+        assert!(stack.filter(|&x| x > 5).is_empty());
+    }
+
+    #[test]
+    fn zip() {
+        let nums = PersistentStack::from_slice(&[3, 2, 1]);
+        let chars = PersistentStack::from_slice(&['a', 'b', 'c']);
+        let zipped = nums.zip(&chars);
+        assert_eq!(
+            zipped.iter().collect::<Vec<_>>(),
+            vec![&(3, 'a'), &(2, 'b'), &(1, 'c')]
+        );
+        assert_eq!(zipped.len(), 3);
+
+        let short = PersistentStack::from_slice(&['x']);
+        let zipped = nums.zip(&short);
+        assert_eq!(zipped.iter().collect::<Vec<_>>(), vec![&(3, 'x')]);
+        assert_eq!(zipped.len(), 1);
+        assert!(nums.zip(&PersistentStack::<char>::new()).is_empty());
+    }
 }